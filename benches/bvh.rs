@@ -0,0 +1,85 @@
+// BVH 와 brute-force 충돌 검사 속도 비교 (삼각형 약 10만 개)
+//
+// cargo bench --no-default-features --bench bvh
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use raytracing_rs::camera::Camera;
+use raytracing_rs::hit::Hittable;
+use raytracing_rs::material::Material;
+use raytracing_rs::light::Light;
+use raytracing_rs::raytracer::Raytracer;
+use raytracing_rs::scene::Scene;
+use raytracing_rs::triangle::Triangle;
+
+const GRID: usize = 224; // 224 * 224 * 2 = 100,352 triangles
+
+// 카메라를 향하는 물결 모양 높이맵 메쉬
+fn wavy_mesh() -> Vec<Box<dyn Hittable>> {
+    let vertex = |i: usize, j: usize| {
+        let x = i as f32 / GRID as f32 * 4.0 - 2.0;
+        let y = j as f32 / GRID as f32 * 3.0 - 1.5;
+        let z = 2.0 + 0.2 * (x * 6.0).sin() * (y * 6.0).cos();
+        cgmath::vec3(x, y, z)
+    };
+    let uv = |i: usize, j: usize| cgmath::vec2(i as f32 / GRID as f32, j as f32 / GRID as f32);
+
+    // 모든 삼각형이 같은 재질을 공유
+    let material = Arc::new(Material {
+        amb: cgmath::vec3(0.1, 0.1, 0.1),
+        diff: cgmath::vec3(0.8, 0.8, 0.8),
+        ..Material::default()
+    });
+
+    let mut objects: Vec<Box<dyn Hittable>> = Vec::with_capacity(GRID * GRID * 2);
+
+    for j in 0..GRID {
+        for i in 0..GRID {
+            let quads = [
+                (vertex(i, j), vertex(i, j + 1), vertex(i + 1, j + 1), uv(i, j), uv(i, j + 1), uv(i + 1, j + 1)),
+                (vertex(i, j), vertex(i + 1, j + 1), vertex(i + 1, j), uv(i, j), uv(i + 1, j + 1), uv(i + 1, j)),
+            ];
+
+            for (v0, v1, v2, uv0, uv1, uv2) in quads {
+                let mut triangle = Triangle::new(v0, v1, v2, uv0, uv1, uv2);
+                triangle.material = material.clone();
+                objects.push(Box::new(triangle));
+            }
+        }
+    }
+
+    objects
+}
+
+fn time_render(raytracer: &Raytracer) -> Duration {
+    let mut img_buff = image::RgbImage::new(raytracer.width as u32, raytracer.height as u32);
+
+    let start = Instant::now();
+    raytracer.render(&mut img_buff);
+    start.elapsed()
+}
+
+fn main() {
+    let scene = Scene {
+        camera: Camera::default(),
+        lights: vec![Light::point(cgmath::vec3(0.0, 1.0, -1.0))],
+        objects: wavy_mesh(),
+    };
+    let triangles = scene.objects.len();
+
+    let start = Instant::now();
+    let mut raytracer = Raytracer::new(64, 48, scene);
+    let build = start.elapsed();
+
+    raytracer.use_bvh = true;
+    let with_bvh = time_render(&raytracer);
+
+    raytracer.use_bvh = false;
+    let brute_force = time_render(&raytracer);
+
+    println!("triangles:        {}", triangles);
+    println!("bvh build:        {:?}", build);
+    println!("render (bvh):     {:?}", with_bvh);
+    println!("render (no bvh):  {:?}", brute_force);
+    println!("speedup:          {:.1}x", brute_force.as_secs_f64() / with_bvh.as_secs_f64());
+}
@@ -0,0 +1,84 @@
+use crate::ray::Ray;
+
+// Axis-aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: cgmath::Vector3<f32>,
+    pub max: cgmath::Vector3<f32>,
+}
+
+impl Aabb {
+    pub fn new(min: cgmath::Vector3<f32>, max: cgmath::Vector3<f32>) -> Self {
+        Aabb { min, max }
+    }
+
+    // 아무것도 포함하지 않는 상자 (union 의 항등원)
+    pub fn empty() -> Self {
+        Aabb {
+            min: cgmath::vec3(f32::MAX, f32::MAX, f32::MAX),
+            max: cgmath::vec3(f32::MIN, f32::MIN, f32::MIN),
+        }
+    }
+
+    pub fn from_points(points: &[cgmath::Vector3<f32>]) -> Self {
+        points.iter().fold(Aabb::empty(), |b, p| b.grow(*p))
+    }
+
+    pub fn grow(&self, p: cgmath::Vector3<f32>) -> Self {
+        Aabb {
+            min: cgmath::vec3(self.min.x.min(p.x), self.min.y.min(p.y), self.min.z.min(p.z)),
+            max: cgmath::vec3(self.max.x.max(p.x), self.max.y.max(p.y), self.max.z.max(p.z)),
+        }
+    }
+
+    pub fn expand(&self, delta: f32) -> Self {
+        let d = cgmath::vec3(delta, delta, delta);
+        Aabb { min: self.min - d, max: self.max + d }
+    }
+
+    pub fn union(&self, other: &Aabb) -> Self {
+        Aabb {
+            min: cgmath::vec3(self.min.x.min(other.min.x), self.min.y.min(other.min.y), self.min.z.min(other.min.z)),
+            max: cgmath::vec3(self.max.x.max(other.max.x), self.max.y.max(other.max.y), self.max.z.max(other.max.z)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    pub fn centroid(&self) -> cgmath::Vector3<f32> {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+
+        let e = self.max - self.min;
+        2.0 * (e.x * e.y + e.y * e.z + e.z * e.x)
+    }
+
+    // 광선이 상자에 들어가는 거리 (slab method)
+    // 충돌하지 않거나 max_d 보다 멀면 None
+    pub fn intersect(&self, ray: &Ray, inv_dir: &cgmath::Vector3<f32>, max_d: f32) -> Option<f32> {
+        let mut t_min = 0.0f32;
+        let mut t_max = max_d;
+
+        for axis in 0..3 {
+            let t0 = (self.min[axis] - ray.start[axis]) * inv_dir[axis];
+            let t1 = (self.max[axis] - ray.start[axis]) * inv_dir[axis];
+
+            // NaN (0 * inf) 은 min/max 에서 무시됨
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+
+        if t_min <= t_max {
+            Some(t_min)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,593 @@
+use cgmath::{ElementWise, InnerSpace};
+
+use crate::bsdf::SurfaceBsdf;
+use crate::camera::Camera;
+use crate::integrator::{Integrator, Splat};
+use crate::light::{pick_light, Light, LightKind};
+use crate::material::Material;
+use crate::medium::MediumStack;
+use crate::path::{next_visible_hit, sample_specular, select_lobe, Lobe};
+use crate::ray::Ray;
+use crate::raytracer::Raytracer;
+use crate::sampler::SampleStream;
+
+// bidirectional path tracing
+// 카메라에서 시작한 경로와 조명에서 시작한 경로를 만들고, 조명 쪽 s 개의 점과 카메라 쪽 t 개의 점을 이어서 빛을 계산
+// 같은 경로를 만들 수 있는 여러 (s, t) 를 multiple importance sampling (power heuristic) 으로 합침
+// 작은 틈으로 들어오는 빛이나 유리 안의 조명처럼 카메라 쪽에서 조명에 닿기 어려운 장면에서 path tracing 보다 빨리 수렴
+// https://graphics.stanford.edu/courses/cs348b-03/papers/veach-chapter10.pdf
+// https://pbr-book.org/3ed-2018/Light_Transport_III_Bidirectional_Methods/Bidirectional_Path_Tracing
+//
+// - 재질, 조명은 path tracing 과 같은 규칙 (amb 는 표면이 내는 빛, 유리는 두 점을 잇는 광선을 막음)
+// - 거울, 유리 (거친 것 포함) 를 고른 점은 다른 점과 잇지 않고 그 방향으로만 이어감
+// - 조명 쪽 경로를 카메라에 바로 잇는 방법 (t = 1, light tracing) 은 다른 픽셀에 닿으므로 splat 으로 더함
+//   (pinhole perspective 카메라만, 유리 구가 바닥에 만드는 caustic 처럼 카메라 쪽에서 만들 수 없는 경로)
+#[derive(Debug, Clone, Copy)]
+pub struct BidirectionalPathTracing {
+    pub max_depth: u32, // 경로가 튕기는 최대 횟수 (조명과 카메라 사이의 점 개수)
+}
+
+impl Default for BidirectionalPathTracing {
+    fn default() -> Self {
+        BidirectionalPathTracing { max_depth: 8 }
+    }
+}
+
+impl Integrator for BidirectionalPathTracing {
+    // splat 을 받을 곳이 없으면 light tracing (t = 1) 없이 계산
+    fn radiance(&self, raytracer: &Raytracer, ray: &Ray, samples: &mut SampleStream) -> cgmath::Vector3<f32> {
+        self.trace(raytracer, ray, samples, None)
+    }
+
+    fn radiance_with_splats(
+        &self,
+        raytracer: &Raytracer,
+        ray: &Ray,
+        samples: &mut SampleStream,
+        splats: &mut Vec<Splat>,
+    ) -> cgmath::Vector3<f32> {
+        self.trace(raytracer, ray, samples, Some(splats))
+    }
+}
+
+impl BidirectionalPathTracing {
+    fn trace(
+        &self,
+        raytracer: &Raytracer,
+        ray: &Ray,
+        samples: &mut SampleStream,
+        mut splats: Option<&mut Vec<Splat>>,
+    ) -> cgmath::Vector3<f32> {
+        let max_depth = self.max_depth as usize;
+        let camera = raytracer.camera();
+        let light_tracing = splats.is_some() && camera.is_pinhole();
+
+        // 카메라 쪽 경로는 조명에 닿는 점까지, 조명 쪽 경로는 조명 다음에 max_depth 개
+        let mut camera_path = vec![Vertex {
+            kind: VertexKind::Camera(camera),
+            point: ray.start,
+            normal: None,
+            beta: cgmath::vec3(1.0, 1.0, 1.0),
+            pdf_fwd: 1.0,
+            pdf_rev: 0.0,
+            delta: false,
+            media: MediumStack::new(),
+        }];
+        let camera_pdf = camera.importance(ray.dir).map_or(1.0, |(_, pdf)| pdf);
+        let camera_ray = Ray { start: ray.start, dir: ray.dir };
+        let mut radiance = random_walk(raytracer, camera_ray, cgmath::vec3(1.0, 1.0, 1.0), camera_pdf, max_depth + 2, true, samples, &mut camera_path);
+
+        let light_path = light_subpath(raytracer, max_depth + 1, samples);
+        let strategies = Strategies { light_tracing };
+
+        for t in 1..=camera_path.len() {
+            for s in 0..=light_path.len() {
+                if s + t < 2 || s + t - 2 > max_depth {
+                    continue;
+                }
+
+                if t == 1 {
+                    // 조명 쪽 경로의 점을 카메라에 바로 이음 (조명이 직접 보이는 경우는 s = 0 으로 계산)
+                    if let (true, Some(splats)) = (light_tracing && s >= 2, splats.as_deref_mut()) {
+                        splats.extend(connect_to_camera(raytracer, &light_path, s, strategies));
+                    }
+                    continue;
+                }
+
+                radiance += connect(raytracer, &camera_path, &light_path, s, t, strategies, samples);
+            }
+        }
+
+        radiance
+    }
+}
+
+// 이 렌더링에서 쓰는 (s, t) (MIS 무게에서 쓰지 않는 방법은 빼야 함)
+#[derive(Clone, Copy)]
+struct Strategies {
+    light_tracing: bool, // t = 1
+}
+
+// 경로 위의 점
+#[derive(Clone)]
+struct Vertex<'a> {
+    kind: VertexKind<'a>,
+    point: cgmath::Vector3<f32>,
+    normal: Option<cgmath::Vector3<f32>>, // 경로가 들어온 쪽을 향한 법선 (카메라, 점 조명은 None)
+
+    // 경로의 시작부터 이 점까지의 throughput (조명 쪽 경로의 시작점은 조명이 내는 빛)
+    beta: cgmath::Vector3<f32>,
+
+    pdf_fwd: f32, // 경로를 만든 방향으로 이 점을 고를 확률 밀도 (면적 기준)
+    pdf_rev: f32, // 반대쪽 끝에서 시작한 경로가 이 점을 고를 확률 밀도 (면적 기준)
+
+    delta: bool, // 거울, 유리를 골라서 다른 점과 이을 수 없는 점
+    media: MediumStack<'a>, // 이 점까지 오는 동안 지나온 매질
+}
+
+#[derive(Clone, Copy)]
+enum VertexKind<'a> {
+    Camera(&'a Camera),
+    Light(&'a Light),
+    Surface(&'a Material, cgmath::Vector3<f32>), // 재질, diffuse 에 곱할 텍스처 색
+}
+
+impl<'a> Vertex<'a> {
+    // 다른 점과 이을 수 있는 표면의 BSDF
+    fn bsdf(&self) -> Option<SurfaceBsdf<'a>> {
+        match (self.kind, self.normal) {
+            (VertexKind::Surface(material, tint), Some(n)) if !self.delta => Some(SurfaceBsdf::new(material, n, tint)),
+            _ => None,
+        }
+    }
+
+    // prev 에서 이 점에 온 경로가 다음 점으로 next 를 고를 확률 밀도 (next 의 면적 기준)
+    fn pdf(&self, prev: Option<&Vertex>, next: &Vertex) -> f32 {
+        let dir = (next.point - self.point).normalize();
+
+        let pdf = match (self.kind, prev) {
+            (VertexKind::Camera(camera), _) => camera.importance(dir).map_or(0.0, |(_, pdf)| pdf),
+            (VertexKind::Light(light), _) => light.emission_direction_pdf(self.point, dir),
+            (VertexKind::Surface(..), Some(prev)) => match self.bsdf() {
+                Some(bsdf) => bsdf.pdf((prev.point - self.point).normalize(), dir),
+                None => 0.0,
+            },
+            (VertexKind::Surface(..), None) => 0.0,
+        };
+
+        convert_density(pdf, self.point, next)
+    }
+
+    fn is_delta_light(&self) -> bool {
+        matches!(self.kind, VertexKind::Light(light) if !light.is_area())
+    }
+}
+
+// from 에서 고른 방향의 확률 밀도 (입체각 기준) 를 to 의 면적 기준으로 바꿈
+fn convert_density(pdf: f32, from: cgmath::Vector3<f32>, to: &Vertex) -> f32 {
+    let w = to.point - from;
+    let d2 = w.magnitude2();
+
+    if d2 <= 0.0 {
+        return 0.0;
+    }
+
+    match to.normal {
+        Some(n) => pdf * n.dot(w).abs() / (d2 * d2.sqrt()),
+        None => pdf / d2,
+    }
+}
+
+// 조명 쪽 경로가 light 위의 한 점에서 시작할 확률 밀도 (조명을 고를 확률 포함)
+fn light_origin_pdf(raytracer: &Raytracer, light: &Light) -> f32 {
+    light.emission_position_pdf() / raytracer.lights().len() as f32
+}
+
+// 조명 하나에서 빛이 나가는 방향을 골라서 경로를 만듦
+fn light_subpath<'a>(raytracer: &'a Raytracer, max_vertices: usize, samples: &mut SampleStream) -> Vec<Vertex<'a>> {
+    let mut path = Vec::new();
+    let lights = raytracer.lights();
+
+    if lights.is_empty() {
+        return path;
+    }
+
+    let light = pick_light(lights, samples.next_1d());
+    let Some(emission) = light.sample_emission(samples.next_2d(), samples.next_2d()) else {
+        return path;
+    };
+
+    path.push(Vertex {
+        kind: VertexKind::Light(light),
+        point: emission.point,
+        normal: emission.normal,
+        beta: emission.radiance,
+        pdf_fwd: light_origin_pdf(raytracer, light),
+        pdf_rev: 0.0,
+        delta: false,
+        media: MediumStack::new(),
+    });
+
+    let cos = emission.normal.map_or(1.0, |n| n.dot(emission.dir));
+    let beta = emission.radiance * (cos / (light_origin_pdf(raytracer, light) * emission.pdf_dir));
+
+    let ray = Ray { start: emission.point + (emission.dir * 1e-4f32), dir: emission.dir };
+    random_walk(raytracer, ray, beta, emission.pdf_dir, max_vertices, false, samples, &mut path);
+
+    // 점 조명의 거리 감쇠는 1 / d^2 이 아닌 attenuation 을 따름
+    if path.len() > 1 {
+        let scale = light.falloff_scale((path[1].point - path[0].point).magnitude());
+
+        for vertex in &mut path[1..] {
+            vertex.beta *= scale;
+        }
+    }
+
+    path
+}
+
+// ray 를 따라가면서 path 에 점을 추가함 (path 에는 시작점이 들어있음)
+// pdf: 시작점에서 ray 방향을 고른 확률 밀도 (입체각 기준)
+// from_camera 이면 면적 조명에 닿을 때 멈추고, 지나간 표면이 내는 빛 (amb) 을 모아서 돌려줌
+#[allow(clippy::too_many_arguments)]
+fn random_walk<'a>(
+    raytracer: &'a Raytracer,
+    mut ray: Ray,
+    mut beta: cgmath::Vector3<f32>,
+    mut pdf: f32,
+    max_vertices: usize,
+    from_camera: bool,
+    samples: &mut SampleStream,
+    path: &mut Vec<Vertex<'a>>,
+) -> cgmath::Vector3<f32> {
+    let mut emitted = cgmath::vec3(0.0, 0.0, 0.0);
+    let mut media = MediumStack::<'a>::new();
+
+    while path.len() < max_vertices {
+        // 카메라 쪽 경로만 면적 조명에 닿음
+        let next = next_visible_hit(raytracer, ray, media, from_camera);
+        let hit = next.hit;
+        ray = next.ray;
+        media = next.media;
+
+        let prev_point = path[path.len() - 1].point;
+
+        if let Some((d, light)) = next.light {
+            let point = ray.start + ray.dir * d;
+            let mut vertex = Vertex {
+                kind: VertexKind::Light(light),
+                point,
+                normal: light.normal_at(point),
+                beta: beta.mul_element_wise(next.transmittance),
+                pdf_fwd: 0.0,
+                pdf_rev: 0.0,
+                delta: false,
+                media,
+            };
+
+            vertex.pdf_fwd = convert_density(pdf, prev_point, &vertex);
+            path.push(vertex);
+            break;
+        }
+
+        if hit.d < 0.0 {
+            break;
+        }
+
+        let Some(material) = hit.material else {
+            break;
+        };
+
+        beta = beta.mul_element_wise(next.transmittance);
+
+        let entering = ray.dir.dot(hit.normal) < 0.0;
+        let normal = if entering { hit.normal } else { -1.0 * hit.normal }.normalize();
+        let v = -ray.dir;
+        let tint = material.diffuse_texture(&hit.uv);
+
+        let surface = 1.0 - material.reflection - material.transparency;
+
+        if from_camera {
+            emitted += beta.mul_element_wise(material.ambient(&hit.uv)) * surface;
+        }
+
+        // 표면 / 거울 반사 / 유리 중 하나를 비율대로 골라둠 (고를 확률과 비율이 같으므로 beta 는 그대로)
+        let lobe = select_lobe(material, samples.next_1d());
+
+        let mut vertex = Vertex {
+            kind: VertexKind::Surface(material, tint),
+            point: hit.point,
+            normal: Some(normal),
+            beta,
+            pdf_fwd: 0.0,
+            pdf_rev: 0.0,
+            delta: lobe != Lobe::Surface,
+            media: media.clone(),
+        };
+
+        vertex.pdf_fwd = convert_density(pdf, prev_point, &vertex);
+        path.push(vertex);
+
+        if path.len() >= max_vertices {
+            break;
+        }
+
+        // 다음 방향, 그리고 반대로 다음 방향에서 왔을 때 v 를 고를 확률 밀도
+        let (dir, pdf_rev) = if lobe == Lobe::Surface {
+            let bsdf = SurfaceBsdf::new(material, normal, tint);
+
+            let Some(sample) = bsdf.sample(v, samples.next_1d(), samples.next_2d()) else {
+                break;
+            };
+
+            if from_camera {
+                beta = beta.mul_element_wise(sample.weight);
+            } else {
+                // 조명 쪽 경로는 빛이 v 에서 와서 sample.dir 로 나감: f(sample.dir, v) * cos(sample.dir)
+                let f = bsdf.eval(sample.dir, v) / normal.dot(v);
+                beta = beta.mul_element_wise(f) * (normal.dot(sample.dir) / sample.pdf);
+            }
+
+            pdf = sample.pdf;
+            (sample.dir, bsdf.pdf(sample.dir, v))
+        } else {
+            let Some((dir, weight, inner)) = sample_specular(material, &media, ray.dir, normal, entering, lobe == Lobe::Glass, samples) else {
+                break;
+            };
+
+            beta *= weight;
+            media = inner;
+            pdf = 0.0;
+            (dir, 0.0)
+        };
+
+        let n = path.len();
+        path[n - 2].pdf_rev = convert_density(pdf_rev, hit.point, &path[n - 2]);
+
+        ray = Ray { start: hit.point + (dir * 1e-4f32), dir };
+    }
+
+    emitted
+}
+
+// 조명 쪽 경로의 앞 s 개 점과 카메라 쪽 경로의 앞 t 개 점을 이은 경로의 기여 (MIS 무게 포함)
+fn connect<'a>(
+    raytracer: &'a Raytracer,
+    camera: &[Vertex<'a>],
+    light: &[Vertex<'a>],
+    s: usize,
+    t: usize,
+    strategies: Strategies,
+    samples: &mut SampleStream,
+) -> cgmath::Vector3<f32> {
+    let zero = cgmath::vec3(0.0, 0.0, 0.0);
+    let pt = &camera[t - 1];
+
+    // 카메라 쪽 경로가 면적 조명에 닿은 경우
+    if s == 0 {
+        let VertexKind::Light(l) = pt.kind else {
+            return zero;
+        };
+
+        return pt.beta.mul_element_wise(l.emission()) * mis_weight(raytracer, camera, light, None, s, t, strategies);
+    }
+
+    let Some(pt_bsdf) = pt.bsdf() else {
+        return zero;
+    };
+    let v = (camera[t - 2].point - pt.point).normalize();
+
+    // 조명 위의 점을 새로 골라서 이음 (path tracing 의 next event estimation)
+    if s == 1 {
+        let lights = raytracer.lights();
+
+        if lights.is_empty() {
+            return zero;
+        }
+
+        let l = pick_light(lights, samples.next_1d());
+        let Some(light_sample) = l.sample(pt.point, samples.next_2d()) else {
+            return zero;
+        };
+
+        let f = pt_bsdf.eval(v, light_sample.dir);
+        if f == zero {
+            return zero;
+        }
+
+        let shadow_ray = Ray { start: pt.point + (light_sample.dir * 1e-4f32), dir: light_sample.dir };
+        if raytracer.find_any_collision(&shadow_ray, light_sample.distance) {
+            return zero;
+        }
+
+        let radiance = light_sample.radiance.mul_element_wise(pt.media.transmittance(light_sample.distance));
+        let contribution = pt.beta.mul_element_wise(f).mul_element_wise(radiance) * lights.len() as f32;
+
+        // 무한히 먼 조명은 이 방법으로만 닿을 수 있음
+        if let LightKind::Directional { .. } = l.kind {
+            return contribution;
+        }
+
+        let point = pt.point + light_sample.dir * light_sample.distance;
+        let sampled = Vertex {
+            kind: VertexKind::Light(l),
+            point,
+            normal: l.normal_at(point),
+            beta: zero,
+            pdf_fwd: light_origin_pdf(raytracer, l),
+            pdf_rev: 0.0,
+            delta: false,
+            media: MediumStack::new(),
+        };
+
+        return contribution * mis_weight(raytracer, camera, light, Some(&sampled), s, t, strategies);
+    }
+
+    let qs = &light[s - 1];
+    let Some(qs_bsdf) = qs.bsdf() else {
+        return zero;
+    };
+    let qs_normal = qs.normal.unwrap();
+
+    let to_light = qs.point - pt.point;
+    let distance = to_light.magnitude();
+    let dir = to_light / distance;
+
+    // 조명 쪽 끝에서 빛이 들어온 방향, 빛이 pt 쪽으로 나가려면 같은 쪽이어야 함
+    let l = (light[s - 2].point - qs.point).normalize();
+    let cos_qs = -dir.dot(qs_normal);
+
+    if cos_qs <= 0.0 {
+        return zero;
+    }
+
+    let f_pt = pt_bsdf.eval(v, dir);
+    let f_qs = qs_bsdf.eval(-dir, l) * (cos_qs / qs_normal.dot(l));
+
+    if f_pt == zero || f_qs == zero {
+        return zero;
+    }
+
+    let shadow_ray = Ray { start: pt.point + (dir * 1e-4f32), dir };
+    if raytracer.find_any_collision(&shadow_ray, distance - 2e-4) {
+        return zero;
+    }
+
+    let contribution = pt.beta.mul_element_wise(f_pt).mul_element_wise(f_qs).mul_element_wise(qs.beta);
+    contribution.mul_element_wise(pt.media.transmittance(distance)) / (distance * distance) * mis_weight(raytracer, camera, light, None, s, t, strategies)
+}
+
+// 조명 쪽 경로의 앞 s 개 점을 카메라에 바로 이은 경로의 기여 (light tracing), 화면 밖이거나 가려지면 None
+fn connect_to_camera<'a>(
+    raytracer: &'a Raytracer,
+    light: &[Vertex<'a>],
+    s: usize,
+    strategies: Strategies,
+) -> Option<Splat> {
+    let camera = raytracer.camera();
+
+    let qs = &light[s - 1];
+    let qs_bsdf = qs.bsdf()?;
+    let qs_normal = qs.normal?;
+
+    let uv = camera.project(qs.point)?;
+
+    let to_camera = camera.pos - qs.point;
+    let distance = to_camera.magnitude();
+    let dir = to_camera / distance;
+
+    let (importance, _) = camera.importance(-dir)?;
+
+    // 조명 쪽 끝에서 빛이 들어온 방향, 빛이 카메라 쪽으로 나가려면 같은 쪽이어야 함
+    let l = (light[s - 2].point - qs.point).normalize();
+    let cos_qs = dir.dot(qs_normal);
+
+    if cos_qs <= 0.0 {
+        return None;
+    }
+
+    let f = qs_bsdf.eval(dir, l) * (cos_qs / qs_normal.dot(l));
+    if f == cgmath::vec3(0.0, 0.0, 0.0) {
+        return None;
+    }
+
+    let shadow_ray = Ray { start: qs.point + (dir * 1e-4f32), dir };
+    if raytracer.find_any_collision(&shadow_ray, distance - 2e-4) {
+        return None;
+    }
+
+    // 카메라 쪽에서 이 점을 고를 확률 밀도 d^2 / cos (pinhole 이라 렌즈 면적은 1)
+    let cos_camera = (-dir).dot((camera.look_at - camera.pos).normalize());
+    let weight = importance * cos_camera / (distance * distance);
+
+    let sampled = Vertex {
+        kind: VertexKind::Camera(camera),
+        point: camera.pos,
+        normal: None,
+        beta: cgmath::vec3(weight, weight, weight),
+        pdf_fwd: 0.0,
+        pdf_rev: 0.0,
+        delta: false,
+        media: MediumStack::new(),
+    };
+
+    let color = qs.beta.mul_element_wise(f) * (weight * mis_weight(raytracer, std::slice::from_ref(&sampled), light, None, s, 1, strategies));
+    let pos = cgmath::vec2(uv.x * raytracer.width as f32, uv.y * raytracer.height as f32);
+
+    Some(Splat { pos, color })
+}
+
+// (s, t) 로 만든 경로의 MIS 무게: 같은 경로를 다른 (s, t) 로 만들 확률 밀도와 비교 (power heuristic)
+// s == 1 이면 sampled 가 조명 쪽 경로 대신 새로 고른 조명 위의 점
+fn mis_weight<'a>(
+    raytracer: &'a Raytracer,
+    camera: &[Vertex<'a>],
+    light: &[Vertex<'a>],
+    sampled: Option<&Vertex<'a>>,
+    s: usize,
+    t: usize,
+    strategies: Strategies,
+) -> f32 {
+    if s + t == 2 {
+        return 1.0;
+    }
+
+    // (pdf_fwd, pdf_rev, delta)
+    let mut camera_pdfs: Vec<_> = camera[..t].iter().map(|v| (v.pdf_fwd, v.pdf_rev, v.delta)).collect();
+    let light_vertices: Vec<&Vertex> = if s == 1 { sampled.into_iter().collect() } else { light[..s].iter().collect() };
+    let mut light_pdfs: Vec<_> = light_vertices.iter().map(|v| (v.pdf_fwd, v.pdf_rev, v.delta)).collect();
+
+    let pt = &camera[t - 1];
+    let pt_minus = if t >= 2 { Some(&camera[t - 2]) } else { None };
+    let qs = light_vertices.last().copied();
+    let qs_minus = if s >= 2 { Some(&light[s - 2]) } else { None };
+
+    // 두 경로를 이으면서 바뀌는 반대 방향 확률 밀도
+    camera_pdfs[t - 1].1 = match (qs, pt.kind) {
+        (Some(qs), _) => qs.pdf(qs_minus, pt),
+        (None, VertexKind::Light(l)) => light_origin_pdf(raytracer, l),
+        (None, _) => 0.0,
+    };
+
+    if let Some(pt_minus) = pt_minus {
+        camera_pdfs[t - 2].1 = pt.pdf(qs, pt_minus);
+    }
+
+    if let Some(qs) = qs {
+        light_pdfs[s - 1].1 = pt.pdf(pt_minus, qs);
+    }
+
+    if let (Some(qs), Some(qs_minus)) = (qs, qs_minus) {
+        light_pdfs[s - 2].1 = qs.pdf(Some(pt), qs_minus);
+    }
+
+    // delta 인 점의 확률 밀도는 0 으로 두었으므로 비율에서는 1 로 취급
+    let remap = |pdf: f32| if pdf != 0.0 { pdf } else { 1.0 };
+    let mut sum = 0.0;
+
+    // 카메라 쪽 점을 하나씩 조명 쪽 경로로 넘긴 방법들 (light tracing 을 쓰지 않으면 t = 1 은 빼고)
+    let mut ratio = 1.0;
+    let min_t = if strategies.light_tracing { 1 } else { 2 };
+
+    for i in (min_t..t).rev() {
+        ratio *= remap(camera_pdfs[i].1) / remap(camera_pdfs[i].0);
+
+        if !camera_pdfs[i].2 && !camera_pdfs[i - 1].2 {
+            sum += ratio * ratio;
+        }
+    }
+
+    // 조명 쪽 점을 하나씩 카메라 쪽 경로로 넘긴 방법들 (s = 0 은 카메라 쪽 경로가 면적 조명에 닿는 경우)
+    ratio = 1.0;
+    for i in (0..s).rev() {
+        ratio *= remap(light_pdfs[i].1) / remap(light_pdfs[i].0);
+
+        let delta_before = if i > 0 { light_pdfs[i - 1].2 } else { light_vertices[0].is_delta_light() };
+
+        if !light_pdfs[i].2 && !delta_before {
+            sum += ratio * ratio;
+        }
+    }
+
+    1.0 / (1.0 + sum)
+}
@@ -0,0 +1,284 @@
+// Renders the scene straight to an image file without opening a window or touching wgpu.
+//
+// cargo run --release --no-default-features --bin headless -- --scene scenes/default.toml --output result.png
+use std::path::PathBuf;
+
+use anyhow::{bail, Context};
+use raytracing_rs::bidirectional::BidirectionalPathTracing;
+use raytracing_rs::filter::{Filter, FilterKind};
+use raytracing_rs::integrator::{Integrator, IntegratorKind};
+use raytracing_rs::occlusion::AmbientOcclusion;
+use raytracing_rs::path::{DirectLighting, PathTracing};
+use raytracing_rs::photon::PhotonMapping;
+use raytracing_rs::raytracer::Raytracer;
+use raytracing_rs::sampler::SamplerKind;
+use raytracing_rs::sampling::{AdaptiveSampling, PixelSampling};
+use raytracing_rs::scene::Scene;
+use raytracing_rs::whitted::Whitted;
+
+struct Args {
+    width: i32,
+    height: i32,
+    scene: PathBuf,
+    output: PathBuf,
+    threads: usize,
+    samples_per_pixel: u32,
+    pixel_sampling: PixelSampling,
+    sampler: SamplerKind,
+    seed: u64,
+    filter: FilterKind,
+    filter_radius: Option<f32>,
+    integrator: IntegratorKind,
+    glossy_samples: Option<u32>,
+    max_depth: Option<u32>,
+    ao_samples: u32,
+    ao_distance: f32,
+    ao_ambient: bool,
+    ao_output: Option<PathBuf>,
+    photons: u32,
+    photon_radius: f32,
+    adaptive: bool,
+    max_samples: u32,
+    threshold: f32,
+    sample_map: Option<PathBuf>,
+}
+
+fn parse_args() -> anyhow::Result<Args> {
+    let mut args = Args {
+        width: 800,
+        height: 600,
+        scene: PathBuf::from("./scenes/default.toml"),
+        output: PathBuf::from("result.png"),
+        threads: 0,
+        samples_per_pixel: 1,
+        pixel_sampling: PixelSampling::Regular,
+        sampler: SamplerKind::Random,
+        seed: 0,
+        filter: FilterKind::Box,
+        filter_radius: None,
+        integrator: IntegratorKind::default(),
+        glossy_samples: None,
+        max_depth: None,
+        ao_samples: AmbientOcclusion::default().samples,
+        ao_distance: AmbientOcclusion::default().distance,
+        ao_ambient: false,
+        ao_output: None,
+        photons: 0,
+        photon_radius: PhotonMapping::default().radius,
+        adaptive: false,
+        max_samples: 64,
+        threshold: 0.01,
+        sample_map: None,
+    };
+
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| iter.next().with_context(|| format!("missing value for {}", name));
+
+        match arg.as_str() {
+            "-w" | "--width" => args.width = value(&arg)?.parse().context("invalid width")?,
+            "-h" | "--height" => args.height = value(&arg)?.parse().context("invalid height")?,
+            "-s" | "--scene" => args.scene = PathBuf::from(value(&arg)?),
+            "-o" | "--output" => args.output = PathBuf::from(value(&arg)?),
+            "-t" | "--threads" => args.threads = value(&arg)?.parse().context("invalid thread count")?,
+            "--spp" => args.samples_per_pixel = value(&arg)?.parse().context("invalid samples per pixel")?,
+            "--sampling" => args.pixel_sampling = value(&arg)?.parse()?,
+            "--sampler" => args.sampler = value(&arg)?.parse()?,
+            "--seed" => args.seed = value(&arg)?.parse().context("invalid seed")?,
+            "--filter" => args.filter = value(&arg)?.parse()?,
+            "--filter-radius" => args.filter_radius = Some(value(&arg)?.parse().context("invalid filter radius")?),
+            "--glossy-samples" => args.glossy_samples = Some(value(&arg)?.parse().context("invalid glossy sample count")?),
+            "--integrator" => args.integrator = value(&arg)?.parse()?,
+            "--max-depth" => args.max_depth = Some(value(&arg)?.parse().context("invalid max depth")?),
+            "--ao-samples" => args.ao_samples = value(&arg)?.parse().context("invalid ambient occlusion sample count")?,
+            "--ao-distance" => args.ao_distance = value(&arg)?.parse().context("invalid ambient occlusion distance")?,
+            "--ao-ambient" => args.ao_ambient = true,
+            "--ao-output" => args.ao_output = Some(PathBuf::from(value(&arg)?)),
+            "--photons" => args.photons = value(&arg)?.parse().context("invalid photon count")?,
+            "--photon-radius" => args.photon_radius = value(&arg)?.parse().context("invalid photon radius")?,
+            "--adaptive" => args.adaptive = true,
+            "--max-spp" => args.max_samples = value(&arg)?.parse().context("invalid max samples per pixel")?,
+            "--threshold" => args.threshold = value(&arg)?.parse().context("invalid threshold")?,
+            "--sample-map" => args.sample_map = Some(PathBuf::from(value(&arg)?)),
+            "--help" => {
+                println!("usage: headless [--scene PATH] [--width N] [--height N] [--output PATH] [--threads N] [--spp N] [--sampling regular|stratified|random]");
+                println!("                [--sampler random|halton|sobol|blue-noise] [--seed N]");
+                println!("                [--filter box|tent|gaussian|mitchell] [--filter-radius R] [--glossy-samples N]");
+                println!("                [--adaptive [--max-spp N] [--threshold T]] [--sample-map PATH]");
+                println!("                [--integrator whitted|ao|direct|path|bdpt|normal|depth|uv|albedo] [--max-depth N]");
+                println!("                [--ao-samples N] [--ao-distance D] [--ao-ambient] [--ao-output PATH]");
+                println!("                [--photons N [--photon-radius R]]");
+                println!("with --ao-ambient, whitted multiplies amb by ambient occlusion; --ao-output also saves an ambient occlusion pass");
+                println!("with --glossy-samples, whitted traces N rays at the first rough surface along each camera ray and one at the rest");
+                println!("with --photons, whitted adds caustics from a photon map of N photons emitted from the lights");
+                println!("with --adaptive, --spp is the number of samples taken before the error estimate is checked (needs --sampling stratified or random)");
+                std::process::exit(0);
+            }
+            _ => bail!("unknown argument: {}", arg),
+        }
+    }
+
+    if args.samples_per_pixel == 0 {
+        bail!("samples per pixel must be at least 1");
+    }
+
+    if args.glossy_samples == Some(0) {
+        bail!("glossy samples must be at least 1");
+    }
+
+    if args.ao_samples == 0 {
+        bail!("ambient occlusion samples must be at least 1");
+    }
+
+    if args.ao_distance.is_nan() || args.ao_distance <= 0.0 {
+        bail!("ambient occlusion distance must be positive");
+    }
+
+    if args.photon_radius.is_nan() || args.photon_radius <= 0.0 {
+        bail!("photon radius must be positive");
+    }
+
+    // amb 를 쓰는 것은 whitted 뿐
+    if args.ao_ambient && args.integrator != IntegratorKind::Whitted {
+        bail!("--ao-ambient only works with --integrator whitted");
+    }
+
+    // 거친 표면에서 여러 광선을 쏘는 것도 whitted 뿐 (다른 integrator 는 샘플마다 하나씩 고름)
+    if args.glossy_samples.is_some() && args.integrator != IntegratorKind::Whitted {
+        bail!("--glossy-samples only works with --integrator whitted");
+    }
+
+    // photon map 으로 caustic 을 더하는 것도 whitted 뿐
+    if args.photons > 0 && args.integrator != IntegratorKind::Whitted {
+        bail!("--photons only works with --integrator whitted");
+    }
+
+    // regular 는 추가로 쏘는 샘플도 처음과 같은 위치라서 오차가 줄지 않음
+    if args.adaptive && args.pixel_sampling == PixelSampling::Regular {
+        bail!("--adaptive needs --sampling stratified or random");
+    }
+
+    // whitted 는 max_depth 를 i32 로 씀
+    if args.max_depth.is_some_and(|d| d > i32::MAX as u32) {
+        bail!("max depth must be at most {}", i32::MAX);
+    }
+
+    if args.threshold.is_nan() || args.threshold < 0.0 {
+        bail!("adaptive sampling threshold must not be negative");
+    }
+
+    if args.adaptive && args.max_samples < args.samples_per_pixel {
+        bail!("max samples per pixel ({}) must be at least --spp ({})", args.max_samples, args.samples_per_pixel);
+    }
+
+    if args.filter_radius.is_some_and(|r| r.is_nan() || r < 0.5) {
+        bail!("filter radius must be at least 0.5 pixels");
+    }
+
+    if args.width <= 0 || args.height <= 0 {
+        bail!("image size must be positive: {}x{}", args.width, args.height);
+    }
+
+    Ok(args)
+}
+
+// --integrator 로 고른 것에 나머지 옵션 (지정하지 않은 것은 각자의 기본값) 을 적용
+fn create_integrator(args: &Args) -> Box<dyn Integrator> {
+    match args.integrator {
+        IntegratorKind::Whitted => {
+            let default = Whitted::default();
+            Box::new(Whitted {
+                max_depth: args.max_depth.map_or(default.max_depth, |d| d as i32),
+                glossy_samples: args.glossy_samples.unwrap_or(default.glossy_samples),
+                ambient_occlusion: args.ao_ambient.then(|| ambient_occlusion(args)),
+            })
+        }
+        IntegratorKind::AmbientOcclusion => Box::new(ambient_occlusion(args)),
+        IntegratorKind::Direct => {
+            let default = DirectLighting::default();
+            Box::new(DirectLighting { max_depth: args.max_depth.unwrap_or(default.max_depth) })
+        }
+        IntegratorKind::Path => {
+            let default = PathTracing::default();
+            Box::new(PathTracing { max_depth: args.max_depth.unwrap_or(default.max_depth), ..default })
+        }
+        IntegratorKind::Bidirectional => {
+            let default = BidirectionalPathTracing::default();
+            Box::new(BidirectionalPathTracing { max_depth: args.max_depth.unwrap_or(default.max_depth) })
+        }
+        kind => kind.create(),
+    }
+}
+
+fn ambient_occlusion(args: &Args) -> AmbientOcclusion {
+    AmbientOcclusion { samples: args.ao_samples, distance: args.ao_distance }
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = parse_args()?;
+
+    println!("width: {}, height: {}", args.width, args.height);
+
+    // image buffer
+    let mut img_buff = image::RgbImage::new(args.width as u32, args.height as u32);
+
+    let scene = Scene::load(&args.scene)?;
+
+    // Start ray tracing
+    let mut ray = Raytracer::new(args.width, args.height, scene);
+    ray.threads = args.threads;
+    ray.samples_per_pixel = args.samples_per_pixel;
+    ray.pixel_sampling = args.pixel_sampling;
+    ray.sampler = args.sampler.create(args.seed);
+    ray.integrator = create_integrator(&args);
+
+    ray.filter = Filter::new(args.filter);
+    if let Some(radius) = args.filter_radius {
+        ray.filter.radius = radius;
+    }
+
+    // 렌더링 전에 조명에서 photon 을 내보내서 caustic 을 모아둠
+    if args.photons > 0 {
+        let photon_mapping = PhotonMapping { photons: args.photons, radius: args.photon_radius };
+        let photon_map = photon_mapping.build(&ray);
+
+        println!("photon map: {} of {} photons stored", photon_map.len(), args.photons);
+        ray.photon_map = Some(photon_map);
+    }
+
+    if args.adaptive {
+        ray.adaptive = Some(AdaptiveSampling {
+            min_samples: args.samples_per_pixel,
+            max_samples: args.max_samples,
+            threshold: args.threshold,
+        });
+    }
+
+    // 픽셀마다 사용한 샘플 수 (흰색일수록 많음)
+    let mut sample_map = args.sample_map.as_ref().map(|_| image::GrayImage::new(args.width as u32, args.height as u32));
+    ray.render_with_sample_map(&mut img_buff, sample_map.as_mut());
+
+    img_buff
+        .save(&args.output)
+        .with_context(|| format!("failed to write {}", args.output.display()))?;
+
+    println!("saved to {}", args.output.display());
+
+    if let (Some(path), Some(sample_map)) = (&args.sample_map, &sample_map) {
+        sample_map.save(path).with_context(|| format!("failed to write {}", path.display()))?;
+        println!("saved sample map to {}", path.display());
+    }
+
+    // 같은 카메라, 같은 픽셀 샘플로 ambient occlusion 만 따로 렌더링 (형태 확인용)
+    if let Some(path) = &args.ao_output {
+        let mut ao_buff = image::RgbImage::new(args.width as u32, args.height as u32);
+        ray.integrator = Box::new(ambient_occlusion(&args));
+        ray.render(&mut ao_buff);
+
+        ao_buff.save(path).with_context(|| format!("failed to write {}", path.display()))?;
+        println!("saved ambient occlusion to {}", path.display());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use std::f32::consts::PI;
+
+use cgmath::{ElementWise, InnerSpace};
+
+use crate::material::{Material, Shading};
+use crate::microfacet;
+use crate::optics;
+use crate::sampling::{orthonormal_basis, sample_cosine_hemisphere};
+
+// path tracing 에서 쓰는 물체 표면의 반사 (BRDF)
+// Whitted 의 phong_color 에 해당하는 부분 (reflection, transparency 를 뺀 나머지) 을 에너지가 보존되도록 정규화한 것
+// diffuse 는 Lambert 의 diff / PI 로 Whitted 와 같으므로, 같은 조명이면 같은 밝기
+pub struct SurfaceBsdf<'a> {
+    material: &'a Material,
+    n: cgmath::Vector3<f32>, // 광선이 오는 쪽을 향한 법선
+    tint: cgmath::Vector3<f32>, // diffuse 에 곱할 텍스처 색
+}
+
+// BSDF 를 따라 고른 방향
+pub struct BsdfSample {
+    pub dir: cgmath::Vector3<f32>,
+    pub weight: cgmath::Vector3<f32>, // f * cos / pdf
+    pub pdf: f32,
+}
+
+impl<'a> SurfaceBsdf<'a> {
+    pub fn new(material: &'a Material, n: cgmath::Vector3<f32>, tint: cgmath::Vector3<f32>) -> Self {
+        SurfaceBsdf { material, n, tint }
+    }
+
+    // v (보는 방향) 에서 본 l (빛이 오는 방향) 의 f(v, l) * cos(n, l)
+    pub fn eval(&self, v: cgmath::Vector3<f32>, l: cgmath::Vector3<f32>) -> cgmath::Vector3<f32> {
+        let cos_l = self.n.dot(l);
+
+        if cos_l <= 0.0 {
+            return cgmath::vec3(0.0, 0.0, 0.0);
+        }
+
+        match &self.material.shading {
+            Shading::Phong => {
+                let m = self.material;
+                let diffuse = m.diff.mul_element_wise(self.tint) / PI;
+
+                // 반사 방향 주변의 적분이 spec 이 되도록 정규화한 Phong
+                let r = optics::reflect(-l, self.n);
+                let specular = m.spec * ((m.alpha + 2.0) / (2.0 * PI) * r.dot(v).max(0.0).powf(m.alpha));
+
+                (diffuse + specular) * cos_l
+            }
+            // pbr.eval 은 Phong 의 diff 와 같은 단위가 되도록 PI 를 곱한 값
+            Shading::MetallicRoughness(pbr) => pbr.eval(self.n, v, l, self.tint) / PI,
+        }
+    }
+
+    // sample() 이 l 을 고를 확률 밀도 (입체각 기준)
+    pub fn pdf(&self, v: cgmath::Vector3<f32>, l: cgmath::Vector3<f32>) -> f32 {
+        let cos_l = self.n.dot(l);
+
+        if cos_l <= 0.0 {
+            return 0.0;
+        }
+
+        let specular = match &self.material.shading {
+            Shading::Phong => {
+                let alpha = self.material.alpha;
+                let r = optics::reflect(-v, self.n);
+
+                (alpha + 1.0) / (2.0 * PI) * r.dot(l).max(0.0).powf(alpha)
+            }
+            Shading::MetallicRoughness(pbr) => {
+                // 보이는 미세면 법선의 분포 D_v(h) 를 반사 방향의 밀도로 바꿈
+                let alpha = pbr.alpha();
+                let n_dot_v = self.n.dot(v).max(1e-4);
+                let h = (v + l).normalize();
+
+                microfacet::smith_g1(n_dot_v, alpha) * microfacet::ggx_distribution(self.n.dot(h).max(0.0), alpha) / (4.0 * n_dot_v)
+            }
+        };
+
+        let p_diffuse = self.diffuse_probability();
+
+        p_diffuse * cos_l / PI + (1.0 - p_diffuse) * specular
+    }
+
+    // u_lobe 로 diffuse 와 specular 중 하나를 고르고, u 로 그 안에서 방향을 고름
+    pub fn sample(&self, v: cgmath::Vector3<f32>, u_lobe: f32, u: cgmath::Vector2<f32>) -> Option<BsdfSample> {
+        let l = if u_lobe < self.diffuse_probability() {
+            sample_cosine_hemisphere(self.n, u)
+        } else {
+            match &self.material.shading {
+                Shading::Phong => sample_phong_lobe(optics::reflect(-v, self.n), self.material.alpha, u),
+                Shading::MetallicRoughness(pbr) => {
+                    let m = microfacet::sample_visible_normal(self.n, v, pbr.alpha(), u);
+                    optics::reflect(-v, m)
+                }
+            }
+        };
+
+        let pdf = self.pdf(v, l);
+
+        if pdf <= 0.0 {
+            return None;
+        }
+
+        Some(BsdfSample { dir: l, weight: self.eval(v, l) / pdf, pdf })
+    }
+
+    // diffuse 쪽 방향을 고를 확률 (나머지는 하이라이트 쪽)
+    fn diffuse_probability(&self) -> f32 {
+        match &self.material.shading {
+            Shading::Phong => {
+                let diffuse = luminance(self.material.diff.mul_element_wise(self.tint));
+                let specular = luminance(self.material.spec);
+
+                if diffuse + specular > 0.0 {
+                    diffuse / (diffuse + specular)
+                } else {
+                    1.0
+                }
+            }
+            Shading::MetallicRoughness(pbr) => 0.5 * (1.0 - pbr.metallic),
+        }
+    }
+}
+
+// r 을 중심으로 cos^alpha 에 비례하는 방향, 확률 밀도는 (alpha + 1) / 2PI * cos^alpha
+fn sample_phong_lobe(r: cgmath::Vector3<f32>, alpha: f32, u: cgmath::Vector2<f32>) -> cgmath::Vector3<f32> {
+    let cos_theta = u.x.powf(1.0 / (alpha + 1.0));
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let phi = 2.0 * PI * u.y;
+
+    let (t, b) = orthonormal_basis(r);
+
+    (t * (sin_theta * phi.cos()) + b * (sin_theta * phi.sin()) + r * cos_theta).normalize()
+}
+
+pub fn luminance(c: cgmath::Vector3<f32>) -> f32 {
+    0.2126 * c.x + 0.7152 * c.y + 0.0722 * c.z
+}
@@ -0,0 +1,273 @@
+use cgmath::ElementWise;
+
+use crate::aabb::Aabb;
+use crate::hit::{Hit, Hittable};
+use crate::ray::Ray;
+
+// SAH 분할 후보를 찾을 때 사용하는 bin 개수
+const SAH_BINS: usize = 16;
+// 이 개수 이하면 분할 비용과 상관없이 leaf 로 만들 수 있음
+const MAX_LEAF_SIZE: usize = 4;
+// 노드 방문 비용 (물체 하나와의 충돌 계산 비용 = 1 기준)
+const TRAVERSAL_COST: f32 = 1.0;
+// 두께가 0인 상자(평면 위의 삼각형)를 놓치지 않기 위한 여유
+const BOUNDS_EPSILON: f32 = 1e-4;
+
+struct BvhNode {
+    bounds: Aabb,
+    // leaf: indices[first..first + count]
+    // interior (count == 0): 왼쪽 자식은 바로 다음 노드, 오른쪽 자식은 nodes[first]
+    first: usize,
+    count: usize,
+}
+
+// Bounding volume hierarchy (surface area heuristic)
+// https://pbr-book.org/3ed-2018/Primitives_and_Intersection_Acceleration/Bounding_Volume_Hierarchies
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    indices: Vec<usize>, // objects 의 인덱스를 leaf 순서대로 정렬
+}
+
+#[derive(Clone, Copy)]
+struct Bin {
+    bounds: Aabb,
+    count: usize,
+}
+
+impl Bvh {
+    pub fn build(objects: &[Box<dyn Hittable>]) -> Bvh {
+        let bounds: Vec<Aabb> = objects.iter().map(|o| o.bounding_box().expand(BOUNDS_EPSILON)).collect();
+        let centroids: Vec<cgmath::Vector3<f32>> = bounds.iter().map(|b| b.centroid()).collect();
+
+        let mut bvh = Bvh {
+            nodes: Vec::with_capacity(objects.len() * 2),
+            indices: (0..objects.len()).collect(),
+        };
+
+        if !objects.is_empty() {
+            bvh.build_recursive(&bounds, &centroids, 0, objects.len());
+        }
+
+        bvh
+    }
+
+    fn build_recursive(&mut self, bounds: &[Aabb], centroids: &[cgmath::Vector3<f32>], start: usize, end: usize) -> usize {
+        let node_index = self.nodes.len();
+        let count = end - start;
+
+        let node_bounds = self.indices[start..end].iter().fold(Aabb::empty(), |b, &i| b.union(&bounds[i]));
+        self.nodes.push(BvhNode { bounds: node_bounds, first: start, count });
+
+        if count == 1 {
+            return node_index;
+        }
+
+        let centroid_bounds = self.indices[start..end].iter().fold(Aabb::empty(), |b, &i| b.grow(centroids[i]));
+
+        let split = self.find_sah_split(bounds, centroids, &centroid_bounds, &node_bounds, start, end);
+
+        let mid = match split {
+            // 분할 비용이 leaf 비용보다 작거나, leaf 로 두기에는 물체가 많은 경우 분할
+            Some((axis, bin, cost)) if cost < count as f32 || count > MAX_LEAF_SIZE => {
+                let min = centroid_bounds.min[axis];
+                let extent = centroid_bounds.max[axis] - min;
+
+                // bin 기준으로 [start, mid) / [mid, end) 로 분할
+                let slice = &mut self.indices[start..end];
+                let mut i = 0;
+                let mut j = slice.len();
+                while i < j {
+                    if Bvh::bin_of(centroids[slice[i]][axis], min, extent) <= bin {
+                        i += 1;
+                    } else {
+                        j -= 1;
+                        slice.swap(i, j);
+                    }
+                }
+                start + i
+            }
+            // 모든 중심점이 같은 위치라 나눌 수 없는데 물체가 많으면 절반으로 나눔
+            None if count > MAX_LEAF_SIZE => start + count / 2,
+            _ => return node_index,
+        };
+
+        self.build_recursive(bounds, centroids, start, mid);
+        let right = self.build_recursive(bounds, centroids, mid, end);
+
+        self.nodes[node_index].first = right;
+        self.nodes[node_index].count = 0;
+
+        node_index
+    }
+
+    fn bin_of(c: f32, min: f32, extent: f32) -> usize {
+        let b = ((c - min) / extent * SAH_BINS as f32) as usize;
+        b.min(SAH_BINS - 1)
+    }
+
+    // (axis, 왼쪽에 포함되는 마지막 bin, 비용)
+    #[allow(clippy::needless_range_loop)]
+    fn find_sah_split(
+        &self,
+        bounds: &[Aabb],
+        centroids: &[cgmath::Vector3<f32>],
+        centroid_bounds: &Aabb,
+        node_bounds: &Aabb,
+        start: usize,
+        end: usize,
+    ) -> Option<(usize, usize, f32)> {
+        let mut best: Option<(usize, usize, f32)> = None;
+        let parent_area = node_bounds.surface_area().max(f32::MIN_POSITIVE);
+
+        for axis in 0..3 {
+            let min = centroid_bounds.min[axis];
+            let extent = centroid_bounds.max[axis] - min;
+
+            if extent <= 0.0 {
+                continue;
+            }
+
+            let mut bins = [Bin { bounds: Aabb::empty(), count: 0 }; SAH_BINS];
+
+            for &i in self.indices[start..end].iter() {
+                let b = Bvh::bin_of(centroids[i][axis], min, extent);
+                bins[b].count += 1;
+                bins[b].bounds = bins[b].bounds.union(&bounds[i]);
+            }
+
+            // 오른쪽부터 누적한 넓이/개수
+            let mut right_area = [0.0f32; SAH_BINS];
+            let mut right_count = [0usize; SAH_BINS];
+            let mut acc = Bin { bounds: Aabb::empty(), count: 0 };
+            for b in (1..SAH_BINS).rev() {
+                acc.bounds = acc.bounds.union(&bins[b].bounds);
+                acc.count += bins[b].count;
+                right_area[b] = acc.bounds.surface_area();
+                right_count[b] = acc.count;
+            }
+
+            let mut acc = Bin { bounds: Aabb::empty(), count: 0 };
+            for b in 0..SAH_BINS - 1 {
+                acc.bounds = acc.bounds.union(&bins[b].bounds);
+                acc.count += bins[b].count;
+
+                if acc.count == 0 || right_count[b + 1] == 0 {
+                    continue;
+                }
+
+                let cost = TRAVERSAL_COST
+                    + (acc.bounds.surface_area() * acc.count as f32
+                    + right_area[b + 1] * right_count[b + 1] as f32) / parent_area;
+
+                if best.is_none_or(|(_, _, c)| cost < c) {
+                    best = Some((axis, b, cost));
+                }
+            }
+        }
+
+        best
+    }
+
+    pub fn closest_hit<'a>(&self, objects: &'a [Box<dyn Hittable>], ray: &Ray) -> Hit<'a> {
+        let mut closest_hit = Hit::new(-1.0, cgmath::vec3(0.0, 0.0, 0.0), cgmath::vec3(0.0, 0.0, 0.0));
+        let mut closest_distance = f32::MAX;
+        let mut closest_index = usize::MAX;
+
+        if self.nodes.is_empty() {
+            return closest_hit;
+        }
+
+        let inv_dir = cgmath::vec3(1.0, 1.0, 1.0).div_element_wise(ray.dir);
+        let mut stack = vec![0usize];
+
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+
+            // 같은 거리에서 인덱스가 작은 물체를 고르기 위해 경계값도 방문
+            if node.bounds.intersect(ray, &inv_dir, closest_distance).is_none() {
+                continue;
+            }
+
+            if node.count > 0 {
+                for &i in self.indices[node.first..node.first + node.count].iter() {
+                    let hit = objects[i].intersect_ray_collision(ray);
+
+                    if hit.d < 0.0 {
+                        continue;
+                    }
+
+                    // brute-force 탐색과 같은 결과를 위해 거리가 같으면 먼저 추가된 물체 선택
+                    if hit.d < closest_distance || (hit.d == closest_distance && i < closest_index) {
+                        closest_distance = hit.d;
+                        closest_index = i;
+
+                        closest_hit = hit;
+                    }
+                }
+            } else {
+                // 가까운 자식을 먼저 방문하도록 먼 자식을 먼저 push
+                let left = node_index + 1;
+                let right = node.first;
+
+                let d_left = self.nodes[left].bounds.intersect(ray, &inv_dir, closest_distance);
+                let d_right = self.nodes[right].bounds.intersect(ray, &inv_dir, closest_distance);
+
+                match (d_left, d_right) {
+                    (Some(l), Some(r)) if l <= r => {
+                        stack.push(right);
+                        stack.push(left);
+                    }
+                    (Some(_), Some(_)) => {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                    (Some(_), None) => stack.push(left),
+                    (None, Some(_)) => stack.push(right),
+                    (None, None) => {}
+                }
+            }
+        }
+
+        closest_hit
+    }
+
+    // max_d 보다 가까운 충돌마다 충돌 결과로 f 를 호출하고, f 가 true 를 반환하면 바로 탐색 중단 (그림자 광선용)
+    // 중단했으면 true
+    pub fn any_hit<'a>(
+        &self,
+        objects: &'a [Box<dyn Hittable>],
+        ray: &Ray,
+        max_d: f32,
+        mut f: impl FnMut(&Hit<'a>) -> bool,
+    ) -> bool {
+        if self.nodes.is_empty() {
+            return false;
+        }
+
+        let inv_dir = cgmath::vec3(1.0, 1.0, 1.0).div_element_wise(ray.dir);
+        let mut stack = vec![0usize];
+
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+
+            if node.bounds.intersect(ray, &inv_dir, max_d).is_none() {
+                continue;
+            }
+
+            if node.count > 0 {
+                for &i in self.indices[node.first..node.first + node.count].iter() {
+                    let hit = objects[i].intersect_ray_collision(ray);
+
+                    if hit.d >= 0.0 && hit.d < max_d && f(&hit) {
+                        return true;
+                    }
+                }
+            } else {
+                stack.push(node.first);
+                stack.push(node_index + 1);
+            }
+        }
+
+        false
+    }
+}
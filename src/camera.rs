@@ -0,0 +1,162 @@
+use std::f32::consts::PI;
+
+use cgmath::InnerSpace;
+
+use crate::ray::Ray;
+use crate::sampling::{sample_disk, sample_polygon};
+
+pub enum Projection {
+    Perspective,
+    // 평행 투영 (도면용), height: 화면 세로 크기 (world 단위)
+    Orthographic { height: f32 },
+    // equirectangular 360° x 180° 파노라마 (VR 미리보기용)
+    // 화면 가운데가 look_at 방향, 가로는 경도, 세로는 위도
+    Panoramic,
+}
+
+pub struct Camera {
+    pub pos: cgmath::Vector3<f32>, // eye position
+    pub look_at: cgmath::Vector3<f32>, // 바라보는 지점
+    pub up: cgmath::Vector3<f32>,
+    pub fov: f32, // vertical field of view (degree), perspective 에만 적용
+    pub projection: Projection,
+
+    // 가로 / 세로, None 이면 이미지 비율을 사용
+    pub aspect: Option<f32>,
+
+    // thin lens depth of field (perspective 에만 적용)
+    // aperture 가 0 이면 pinhole 카메라 (모든 곳에 초점)
+    pub aperture: f32, // 렌즈 반지름
+    pub focus_distance: f32, // 초점이 맞는 평면까지의 거리 (시선 방향)
+    pub blades: u32, // 조리개 날개 수 (3 이상이면 다각형 bokeh, 0 이면 원형)
+}
+
+impl Default for Camera {
+    // 예전 고정 카메라: (0, 0, -1.5) 에서 z=0 의 높이 2 스크린을 바라봄
+    fn default() -> Self {
+        Camera {
+            pos: cgmath::vec3(0.0, 0.0, -1.5),
+            look_at: cgmath::vec3(0.0, 0.0, 0.0),
+            up: cgmath::vec3(0.0, 1.0, 0.0),
+            fov: 2.0 * (1.0f32 / 1.5).atan().to_degrees(),
+            projection: Projection::Perspective,
+            aspect: None,
+            aperture: 0.0,
+            focus_distance: 1.5,
+            blades: 0,
+        }
+    }
+}
+
+impl Camera {
+    // 카메라 좌표계 (right, up, forward)
+    fn basis(&self) -> (cgmath::Vector3<f32>, cgmath::Vector3<f32>, cgmath::Vector3<f32>) {
+        let forward = (self.look_at - self.pos).normalize();
+        let right = self.up.cross(forward).normalize();
+        let up = forward.cross(right);
+
+        (right, up, forward)
+    }
+
+    // uv: 화면 위의 위치 [0, 1] x [0, 1], (0, 0) 이 왼쪽 위
+    // lens: 렌즈 위의 위치를 고르는 [0, 1)^2 난수 (aperture 가 0 이면 무시)
+    pub fn generate_ray(&self, uv: cgmath::Vector2<f32>, lens: cgmath::Vector2<f32>) -> Ray {
+        let (right, up, forward) = self.basis();
+
+        match self.projection {
+            Projection::Perspective => {}
+            Projection::Orthographic { height } => {
+                // 모든 광선이 시선 방향과 평행, 시작점만 화면 위에서 이동
+                let half_h = height * 0.5;
+                let half_w = half_h * self.aspect.unwrap_or(1.0);
+
+                let start = self.pos
+                    + right * ((uv.x * 2.0 - 1.0) * half_w)
+                    + up * ((1.0 - uv.y * 2.0) * half_h);
+
+                return Ray { start, dir: forward };
+            }
+            Projection::Panoramic => {
+                let phi = (uv.x - 0.5) * 2.0 * PI; // 경도
+                let theta = (0.5 - uv.y) * PI; // 위도
+
+                let dir = forward * (theta.cos() * phi.cos())
+                    + right * (theta.cos() * phi.sin())
+                    + up * theta.sin();
+
+                return Ray { start: self.pos, dir: dir.normalize() };
+            }
+        }
+
+        let half_h = (self.fov.to_radians() * 0.5).tan();
+        let half_w = half_h * self.aspect.unwrap_or(1.0);
+
+        let dir = forward
+            + right * ((uv.x * 2.0 - 1.0) * half_w)
+            + up * ((1.0 - uv.y * 2.0) * half_h);
+
+        if self.aperture <= 0.0 {
+            return Ray { start: self.pos, dir: dir.normalize() };
+        }
+
+        // 렌즈 위의 한 점에서 초점 평면 위의 같은 점을 향하는 광선
+        // 초점 평면 위의 물체는 선명하고, 멀어질수록 흐려짐
+        let focus_point = self.pos + dir * self.focus_distance;
+
+        let lens_pos = self.sample_lens(lens) * self.aperture;
+        let start = self.pos + right * lens_pos.x + up * lens_pos.y;
+
+        Ray { start, dir: (focus_point - start).normalize() }
+    }
+
+    // pinhole perspective 카메라만 world 의 점이 화면 어디에 보이는지 계산할 수 있음 (light tracing)
+    pub fn is_pinhole(&self) -> bool {
+        matches!(self.projection, Projection::Perspective) && self.aperture <= 0.0
+    }
+
+    // point 가 보이는 화면 위의 위치 uv ([0, 1] x [0, 1]), 화면 밖이거나 pinhole 카메라가 아니면 None
+    pub fn project(&self, point: cgmath::Vector3<f32>) -> Option<cgmath::Vector2<f32>> {
+        if !self.is_pinhole() {
+            return None;
+        }
+
+        let (right, up, forward) = self.basis();
+        let d = point - self.pos;
+        let z = d.dot(forward);
+
+        if z <= 0.0 {
+            return None;
+        }
+
+        let half_h = (self.fov.to_radians() * 0.5).tan();
+        let half_w = half_h * self.aspect.unwrap_or(1.0);
+
+        // generate_ray 의 반대
+        let uv = cgmath::vec2((d.dot(right) / (z * half_w) + 1.0) * 0.5, (1.0 - d.dot(up) / (z * half_h)) * 0.5);
+
+        ((0.0..=1.0).contains(&uv.x) && (0.0..=1.0).contains(&uv.y)).then_some(uv)
+    }
+
+    // pinhole 카메라에서 dir 방향으로 나가는 광선의 importance We 와, 화면 위의 균일한 점으로 그 방향을 고를 확률 밀도 (입체각 기준)
+    // 거리 1 에서 화면의 면적이 A 이면 We = 1 / (A cos^4), 확률 밀도는 1 / (A cos^3)
+    // https://pbr-book.org/3ed-2018/Light_Transport_III_Bidirectional_Methods/The_Path-Space_Measurement_Equation
+    pub fn importance(&self, dir: cgmath::Vector3<f32>) -> Option<(f32, f32)> {
+        self.project(self.pos + dir)?;
+
+        let (_, _, forward) = self.basis();
+        let cos = dir.dot(forward);
+
+        let half_h = (self.fov.to_radians() * 0.5).tan();
+        let area = 4.0 * half_h * half_h * self.aspect.unwrap_or(1.0);
+
+        Some((1.0 / (area * cos * cos * cos * cos), 1.0 / (area * cos * cos * cos)))
+    }
+
+    fn sample_lens(&self, u: cgmath::Vector2<f32>) -> cgmath::Vector2<f32> {
+        if self.blades >= 3 {
+            sample_polygon(self.blades, u)
+        } else {
+            sample_disk(u)
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+
+use crate::filter::Filter;
+use crate::integrator::Splat;
+
+// 필터 가중치를 곱한 샘플 색의 합과 가중치의 합
+#[derive(Clone, Copy)]
+struct FilmPixel {
+    color: cgmath::Vector3<f32>,
+    weight: f32,
+}
+
+// 이미지의 [x0, x0 + width) x [y0, y0 + height) 영역에 샘플을 모으는 곳
+// tile 마다 필터 반지름만큼 넓은 film 을 만들고, 다 끝나면 전체 film 에 정해진 순서로 더함
+// (tile 경계에서도 필터가 이어지고, 결과가 스레드 수와 무관)
+pub struct Film {
+    pub x0: i32,
+    pub y0: i32,
+    pub width: i32,
+    pub height: i32,
+    pixels: Vec<FilmPixel>,
+}
+
+impl Film {
+    pub fn new(x0: i32, y0: i32, width: i32, height: i32) -> Self {
+        let empty = FilmPixel { color: cgmath::vec3(0.0, 0.0, 0.0), weight: 0.0 };
+        Film { x0, y0, width, height, pixels: vec![empty; (width.max(0) * height.max(0)) as usize] }
+    }
+
+    // pos: 이미지 위의 연속 좌표 (픽셀 (i, j) 의 중심은 (i + 0.5, j + 0.5))
+    // 필터 반지름 안에 중심이 있는 모든 픽셀에 가중치를 곱해 더함
+    pub fn add_sample(&mut self, pos: cgmath::Vector2<f32>, color: cgmath::Vector3<f32>, filter: &Filter) {
+        let x_min = ((pos.x - 0.5 - filter.radius).ceil() as i32).max(self.x0);
+        let x_max = ((pos.x - 0.5 + filter.radius).floor() as i32).min(self.x0 + self.width - 1);
+        let y_min = ((pos.y - 0.5 - filter.radius).ceil() as i32).max(self.y0);
+        let y_max = ((pos.y - 0.5 + filter.radius).floor() as i32).min(self.y0 + self.height - 1);
+
+        for j in y_min..=y_max {
+            for i in x_min..=x_max {
+                let weight = filter.weight(pos.x - (i as f32 + 0.5), pos.y - (j as f32 + 0.5));
+                if weight == 0.0 {
+                    continue;
+                }
+
+                let pixel = &mut self.pixels[((j - self.y0) * self.width + (i - self.x0)) as usize];
+                pixel.color += color * weight;
+                pixel.weight += weight;
+            }
+        }
+    }
+
+    // 겹치는 영역의 값을 더함
+    pub fn merge(&mut self, other: &Film) {
+        let x_min = self.x0.max(other.x0);
+        let x_max = (self.x0 + self.width).min(other.x0 + other.width);
+        let y_min = self.y0.max(other.y0);
+        let y_max = (self.y0 + self.height).min(other.y0 + other.height);
+
+        for j in y_min..y_max {
+            for i in x_min..x_max {
+                let src = other.pixels[((j - other.y0) * other.width + (i - other.x0)) as usize];
+                let dst = &mut self.pixels[((j - self.y0) * self.width + (i - self.x0)) as usize];
+
+                dst.color += src.color;
+                dst.weight += src.weight;
+            }
+        }
+    }
+
+    // 픽셀 (i, j) 의 최종 색 (가중치 합이 0 이하면 검은색)
+    pub fn color(&self, i: i32, j: i32) -> cgmath::Vector3<f32> {
+        let pixel = self.pixels[((j - self.y0) * self.width + (i - self.x0)) as usize];
+
+        if pixel.weight > 0.0 {
+            pixel.color / pixel.weight
+        } else {
+            cgmath::vec3(0.0, 0.0, 0.0)
+        }
+    }
+}
+
+// light tracing 처럼 픽셀과 상관없이 더하는 빛 (필터 없이 닿은 픽셀에만 더함)
+// tile 마다 모은 splat 을 tile 번호 순서로 더해서 결과가 스레드 수와 무관
+pub struct SplatFilm {
+    pub width: i32,
+    pub height: i32,
+    pixels: Vec<cgmath::Vector3<f32>>,
+
+    // 아직 더하지 않은 tile 들 (앞 번호의 tile 이 끝나기를 기다림)
+    next_tile: usize,
+    pending: BTreeMap<usize, Vec<Splat>>,
+}
+
+impl SplatFilm {
+    pub fn new(width: i32, height: i32) -> Self {
+        SplatFilm {
+            width,
+            height,
+            pixels: vec![cgmath::vec3(0.0, 0.0, 0.0); (width.max(0) * height.max(0)) as usize],
+            next_tile: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    // tile 번호 t 에서 나온 splat 들, 이어지는 번호가 모두 모이면 그 순서대로 더함
+    pub fn add_tile(&mut self, t: usize, splats: Vec<Splat>) {
+        self.pending.insert(t, splats);
+
+        while let Some(splats) = self.pending.remove(&self.next_tile) {
+            for splat in splats {
+                let i = splat.pos.x.floor() as i32;
+                let j = splat.pos.y.floor() as i32;
+
+                if (0..self.width).contains(&i) && (0..self.height).contains(&j) {
+                    self.pixels[(j * self.width + i) as usize] += splat.color;
+                }
+            }
+
+            self.next_tile += 1;
+        }
+    }
+
+    // 픽셀 (i, j) 에 더해진 빛의 합
+    pub fn color(&self, i: i32, j: i32) -> cgmath::Vector3<f32> {
+        self.pixels[(j * self.width + i) as usize]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pixels.iter().all(|c| *c == cgmath::vec3(0.0, 0.0, 0.0))
+    }
+}
@@ -0,0 +1,100 @@
+use std::str::FromStr;
+
+// 샘플이 주변 픽셀에 기여하는 가중치 (reconstruction filter)
+// 픽셀 색 = sum(weight * 샘플 색) / sum(weight)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterKind {
+    // 반지름 안이면 모두 같은 가중치 (반지름 0.5 면 자기 픽셀의 샘플 평균)
+    #[default]
+    Box,
+    // 중심에서 멀어질수록 선형으로 감소
+    Tent,
+    // 부드럽지만 약간 흐림
+    Gaussian,
+    // Mitchell-Netravali (B = C = 1/3), 음수 부분이 있어서 가장 선명
+    // https://www.cs.utexas.edu/~fussell/courses/cs384g-fall2013/lectures/mitchell/Mitchell.pdf
+    Mitchell,
+}
+
+impl FilterKind {
+    pub fn default_radius(&self) -> f32 {
+        match self {
+            FilterKind::Box => 0.5,
+            FilterKind::Tent => 1.0,
+            FilterKind::Gaussian => 1.5,
+            FilterKind::Mitchell => 2.0,
+        }
+    }
+}
+
+impl FromStr for FilterKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "box" => Ok(FilterKind::Box),
+            "tent" => Ok(FilterKind::Tent),
+            "gaussian" => Ok(FilterKind::Gaussian),
+            "mitchell" => Ok(FilterKind::Mitchell),
+            _ => Err(anyhow::anyhow!("unknown filter '{}', expected box, tent, gaussian or mitchell", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Filter {
+    pub kind: FilterKind,
+    pub radius: f32, // 픽셀 단위
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Filter::new(FilterKind::default())
+    }
+}
+
+impl Filter {
+    pub fn new(kind: FilterKind) -> Self {
+        Filter { kind, radius: kind.default_radius() }
+    }
+
+    // 샘플과 픽셀 중심 사이의 거리 (dx, dy) 에 대한 가중치, x 와 y 를 따로 계산해서 곱함
+    pub fn weight(&self, dx: f32, dy: f32) -> f32 {
+        self.weight_1d(dx) * self.weight_1d(dy)
+    }
+
+    fn weight_1d(&self, x: f32) -> f32 {
+        let x = x.abs();
+
+        if x > self.radius {
+            return 0.0;
+        }
+
+        match self.kind {
+            FilterKind::Box => 1.0,
+            FilterKind::Tent => self.radius - x,
+            FilterKind::Gaussian => {
+                // 반지름에서 0 이 되도록 가장자리 값을 뺌
+                let sigma = self.radius / 3.0;
+                let gaussian = |x: f32| (-x * x / (2.0 * sigma * sigma)).exp();
+
+                gaussian(x) - gaussian(self.radius)
+            }
+            FilterKind::Mitchell => {
+                // 원래 정의는 [-2, 2] 이므로 반지름에 맞게 늘림
+                const B: f32 = 1.0 / 3.0;
+                const C: f32 = 1.0 / 3.0;
+
+                let x = x * 2.0 / self.radius;
+
+                if x < 1.0 {
+                    ((12.0 - 9.0 * B - 6.0 * C) * x * x * x + (-18.0 + 12.0 * B + 6.0 * C) * x * x + (6.0 - 2.0 * B)) / 6.0
+                } else {
+                    ((-B - 6.0 * C) * x * x * x + (6.0 * B + 30.0 * C) * x * x + (-12.0 * B - 48.0 * C) * x
+                        + (8.0 * B + 24.0 * C))
+                        / 6.0
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,6 @@
+use crate::aabb::Aabb;
+use crate::material::Material;
 use crate::ray::Ray;
-use crate::sphere::Sphere;
-use crate::square::Square;
-use crate::texture::Texture;
-use crate::triangle::Triangle;
-
-pub enum Object {
-    Triangle(Triangle),
-    Sphere(Sphere),
-    Square(Square),
-}
 
 pub struct Hit<'a> {
     pub d: f32, // distance from the start of ray
@@ -19,21 +11,8 @@ pub struct Hit<'a> {
 
     pub uv: cgmath::Vector2<f32>, // texture coordinates
 
-    // 나중에 물체의 재질 등을 가져오기 위한 포인터
-    pub object: Option<&'a dyn Hittable>
-}
-
-pub struct LightProperty {
-    pub amb: cgmath::Vector3<f32>,
-    pub diff: cgmath::Vector3<f32>,
-    pub spec: cgmath::Vector3<f32>,
-    pub ks: f32,
-    pub alpha: f32,
-}
-
-pub struct TextureProperty {
-    pub amb_tex: Option<Texture>,
-    pub dif_tex: Option<Texture>,
+    // 충돌한 물체의 재질
+    pub material: Option<&'a Material>
 }
 
 impl Hit<'_> {
@@ -44,17 +23,15 @@ impl Hit<'_> {
             normal,
             w: cgmath::vec2(0.0, 0.0),
             uv: cgmath::vec2(0.0, 0.0),
-            object: None
+            material: None
         }
     }
 }
 
 // interface 같은 역할. 각 Object 에서 intersect_ray_collision 메소드 구현
-pub trait Hittable {
-    fn intersect_ray_collision(&self, ray: &Ray) -> Hit;
-    fn get_light_color_properties(&self) -> LightProperty;
-    fn get_ambient_texture(&self) -> &Option<Texture>;
-    fn get_diffuse_texture(&self) -> &Option<Texture>;
-    fn get_reflection(&self) -> f32;
-    fn get_transparency(&self) -> f32;
+// 재질은 충돌 결과 (Hit::material) 로 넘겨주므로 도형은 기하만 구현하면 됨
+// 여러 렌더링 스레드가 장면을 공유하므로 Send + Sync
+pub trait Hittable: Send + Sync {
+    fn intersect_ray_collision(&self, ray: &Ray) -> Hit<'_>;
+    fn bounding_box(&self) -> Aabb;
 }
\ No newline at end of file
@@ -0,0 +1,134 @@
+use std::str::FromStr;
+
+use cgmath::{ElementWise, InnerSpace};
+
+use crate::bidirectional::BidirectionalPathTracing;
+use crate::material::Shading;
+use crate::occlusion::AmbientOcclusion;
+use crate::path::{DirectLighting, PathTracing};
+use crate::ray::Ray;
+use crate::raytracer::Raytracer;
+use crate::sampler::SampleStream;
+use crate::whitted::Whitted;
+
+// 카메라 광선 하나를 따라 들어오는 빛 (픽셀 샘플 하나의 색) 을 계산하는 방법
+// render 는 픽셀 샘플마다 이것만 호출하므로, 새 방법은 이 trait 을 구현해서 Raytracer::integrator 에 넣으면 됨
+pub trait Integrator: Send + Sync {
+    // samples: 픽셀 위치와 렌즈에 쓰고 남은 차원부터 이어서 꺼내 쓰는 곳
+    fn radiance(&self, raytracer: &Raytracer, ray: &Ray, samples: &mut SampleStream) -> cgmath::Vector3<f32>;
+
+    // radiance 와 같지만, 카메라 광선과 상관없는 픽셀에 닿는 빛 (light tracing) 을 splats 에 넣을 수 있음 (render 는 이것을 호출)
+    fn radiance_with_splats(
+        &self,
+        raytracer: &Raytracer,
+        ray: &Ray,
+        samples: &mut SampleStream,
+        _splats: &mut Vec<Splat>,
+    ) -> cgmath::Vector3<f32> {
+        self.radiance(raytracer, ray, samples)
+    }
+}
+
+// 다른 픽셀에 더할 빛
+// render 가 모든 splat 을 픽셀마다 더한 뒤 픽셀당 평균 샘플 수로 나눠서 이미지에 더함
+pub struct Splat {
+    pub pos: cgmath::Vector2<f32>, // 이미지 위의 연속 좌표 (Film::add_sample 과 같음)
+    pub color: cgmath::Vector3<f32>,
+}
+
+// 설정 (headless 의 --integrator) 에서 이름으로 고를 수 있는 integrator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntegratorKind {
+    // Phong + 거울 반사/굴절 (기본값)
+    #[default]
+    Whitted,
+    // 가려진 정도만 흑백으로
+    AmbientOcclusion,
+    // 조명에서 바로 온 빛만
+    Direct,
+    // 간접광까지 (global illumination)
+    Path,
+    // 조명에서 시작한 경로와 이어서 (조명에 닿기 어려운 장면)
+    Bidirectional,
+    // 처음 닿은 표면의 정보를 색으로
+    Debug(DebugView),
+}
+
+impl IntegratorKind {
+    // 기본 설정으로 만듦 (max_depth 등은 만든 구조체를 직접 바꾸면 됨)
+    pub fn create(&self) -> Box<dyn Integrator> {
+        match self {
+            IntegratorKind::Whitted => Box::new(Whitted::default()),
+            IntegratorKind::AmbientOcclusion => Box::new(AmbientOcclusion::default()),
+            IntegratorKind::Direct => Box::new(DirectLighting::default()),
+            IntegratorKind::Path => Box::new(PathTracing::default()),
+            IntegratorKind::Bidirectional => Box::new(BidirectionalPathTracing::default()),
+            IntegratorKind::Debug(view) => Box::new(*view),
+        }
+    }
+}
+
+impl FromStr for IntegratorKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "whitted" => Ok(IntegratorKind::Whitted),
+            "ao" => Ok(IntegratorKind::AmbientOcclusion),
+            "direct" => Ok(IntegratorKind::Direct),
+            "path" => Ok(IntegratorKind::Path),
+            "bdpt" => Ok(IntegratorKind::Bidirectional),
+            "normal" => Ok(IntegratorKind::Debug(DebugView::Normal)),
+            "depth" => Ok(IntegratorKind::Debug(DebugView::Depth)),
+            "uv" => Ok(IntegratorKind::Debug(DebugView::Uv)),
+            "albedo" => Ok(IntegratorKind::Debug(DebugView::Albedo)),
+            _ => Err(anyhow::anyhow!(
+                "unknown integrator '{}', expected whitted, ao, direct, path, bdpt, normal, depth, uv or albedo",
+                s
+            )),
+        }
+    }
+}
+
+// 디버깅용으로 처음 닿은 표면의 정보를 색으로 보여줌 (아무것도 없으면 검은색)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugView {
+    // 법선 [-1, 1] -> [0, 1]
+    Normal,
+    // 카메라에서 가까울수록 밝게 1 / (1 + 거리)
+    Depth,
+    // 텍스처 좌표 (u, v, 0)
+    Uv,
+    // 조명과 상관없는 표면의 색 (Phong 은 diff, metallic-roughness 는 base_color, 텍스처 포함)
+    Albedo,
+}
+
+impl Integrator for DebugView {
+    fn radiance(&self, raytracer: &Raytracer, ray: &Ray, _samples: &mut SampleStream) -> cgmath::Vector3<f32> {
+        let hit = raytracer.find_closest_collision(ray);
+
+        if hit.d < 0.0 {
+            return cgmath::vec3(0.0, 0.0, 0.0);
+        }
+
+        match self {
+            DebugView::Normal => (hit.normal.normalize() + cgmath::vec3(1.0, 1.0, 1.0)) * 0.5,
+            DebugView::Depth => {
+                let v = 1.0 / (1.0 + hit.d);
+                cgmath::vec3(v, v, v)
+            }
+            DebugView::Uv => cgmath::vec3(hit.uv.x.rem_euclid(1.0), hit.uv.y.rem_euclid(1.0), 0.0),
+            DebugView::Albedo => match hit.material {
+                Some(material) => {
+                    let color = match &material.shading {
+                        Shading::Phong => material.diff,
+                        Shading::MetallicRoughness(pbr) => pbr.base_color,
+                    };
+
+                    color.mul_element_wise(material.diffuse_texture(&hit.uv))
+                }
+                None => cgmath::vec3(0.0, 0.0, 0.0),
+            },
+        }
+    }
+}
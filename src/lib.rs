@@ -1,14 +1,36 @@
 // https://doc.rust-kr.org/ch07-00-managing-growing-projects-with-packages-crates-and-modules.html
 pub mod ray;
 pub mod raytracer;
+pub mod integrator;
+pub mod whitted;
 pub mod sphere;
 pub mod hit;
+pub mod material;
+pub mod optics;
+pub mod microfacet;
+pub mod bsdf;
+pub mod medium;
+pub mod path;
+pub mod bidirectional;
+pub mod occlusion;
+pub mod photon;
 pub mod light;
+#[cfg(feature = "viewer")]
 pub mod state;
+#[cfg(feature = "viewer")]
 pub mod wgpu_texture_util;
 pub mod triangle;
 pub mod square;
 pub mod texture;
+pub mod scene;
+pub mod aabb;
+pub mod bvh;
+pub mod random;
+pub mod sampling;
+pub mod sampler;
+pub mod filter;
+pub mod film;
+pub mod camera;
 /*
 * WebAssembly (WASM) 시 도전!
 */
@@ -1,3 +1,423 @@
+use std::f32::consts::PI;
+
+use cgmath::InnerSpace;
+
+use crate::ray::Ray;
+use crate::sampling::{orthonormal_basis, sample_cone, sample_cosine_hemisphere, sample_disk};
+
+pub enum LightKind {
+    // 한 점에서 모든 방향으로 퍼지는 조명
+    Point {
+        pos: cgmath::Vector3<f32>, // position
+    },
+    // 태양처럼 아주 멀리 있어서 모든 곳에 같은 방향으로 들어오는 조명
+    Directional {
+        dir: cgmath::Vector3<f32>, // 빛이 진행하는 방향
+    },
+    // 원뿔 모양으로 비추는 조명
+    Spot {
+        pos: cgmath::Vector3<f32>,
+        dir: cgmath::Vector3<f32>, // 원뿔 축 방향
+        cone_angle: f32, // 원뿔의 반각 (degree)
+        soft_edge: f32, // 원뿔 가장자리에서 밝기가 부드럽게 줄어드는 각도 범위 (degree)
+    },
+
+    // 면적이 있는 조명 (soft shadow)
+    // rect, disk 는 한쪽 면으로만 빛을 냄
+    Rect {
+        corner: cgmath::Vector3<f32>,
+        edge_u: cgmath::Vector3<f32>,
+        edge_v: cgmath::Vector3<f32>, // 빛은 edge_u x edge_v 방향으로 나감
+    },
+    Disk {
+        center: cgmath::Vector3<f32>,
+        normal: cgmath::Vector3<f32>, // 빛이 나가는 방향
+        radius: f32,
+    },
+    Sphere {
+        center: cgmath::Vector3<f32>,
+        radius: f32,
+    },
+}
+
 pub struct Light {
-    pub pos: cgmath::Vector3<f32>, // position
-}
\ No newline at end of file
+    pub kind: LightKind,
+    pub color: cgmath::Vector3<f32>,
+    pub intensity: f32,
+
+    // 거리 감쇠 1 / (constant + linear * d + quadratic * d^2), point/spot 에만 적용
+    // 면적 조명은 면적과 각도로 자연스럽게 감쇠
+    pub attenuation: cgmath::Vector3<f32>,
+
+    // 면적 조명에서 한 지점마다 쏘는 그림자 광선 개수
+    pub samples: u32,
+}
+
+// 한 지점에서 본 조명 정보
+pub struct LightSample {
+    pub dir: cgmath::Vector3<f32>, // 조명을 향하는 단위 벡터
+    pub distance: f32, // 조명까지 거리 (그림자 광선의 최대 거리)
+    pub radiance: cgmath::Vector3<f32>, // 도달하는 빛의 색 * 세기
+}
+
+// 조명에서 나가는 빛 하나
+pub struct EmissionSample {
+    pub point: cgmath::Vector3<f32>, // 빛이 나가는 점
+    pub normal: Option<cgmath::Vector3<f32>>, // 면적 조명 표면의 법선 (점 조명은 None)
+    pub dir: cgmath::Vector3<f32>, // 빛이 나가는 방향
+    pub radiance: cgmath::Vector3<f32>, // 그 방향으로 나가는 빛 (점 조명은 세기)
+    pub pdf_pos: f32, // point 를 고른 확률 밀도 (면적 기준, 점 조명은 1)
+    pub pdf_dir: f32, // dir 을 고른 확률 밀도 (입체각 기준)
+}
+
+impl Light {
+    // 감쇠 없는 흰색 점 조명 (장면 파일의 기본값과 같은 세기 PI)
+    pub fn point(pos: cgmath::Vector3<f32>) -> Self {
+        Light {
+            kind: LightKind::Point { pos },
+            color: cgmath::vec3(1.0, 1.0, 1.0),
+            intensity: PI,
+            attenuation: cgmath::vec3(1.0, 0.0, 0.0),
+            samples: 1,
+        }
+    }
+
+    pub fn is_area(&self) -> bool {
+        matches!(self.kind, LightKind::Rect { .. } | LightKind::Disk { .. } | LightKind::Sphere { .. })
+    }
+
+    // 한 지점에서 쏠 그림자 광선 개수
+    pub fn sample_count(&self) -> u32 {
+        if self.is_area() {
+            self.samples.max(1)
+        } else {
+            1
+        }
+    }
+
+    // u: 면적 조명 위의 위치를 고르는 [0, 1)^2 난수 (점 조명은 무시)
+    // 면적 조명은 radiance 를 sample_count() 개 평균내면 조명 전체의 기여가 됨
+    pub fn sample(&self, point: cgmath::Vector3<f32>, u: cgmath::Vector2<f32>) -> Option<LightSample> {
+        let (dir, distance, factor) = match self.kind {
+            LightKind::Point { pos } => {
+                let to_light = pos - point;
+                let d = to_light.magnitude();
+
+                // 조명과 같은 위치에서는 방향이 없음
+                if d <= 1e-6 {
+                    return None;
+                }
+
+                (to_light / d, d, self.falloff(d))
+            }
+            LightKind::Directional { dir } => {
+                (-dir.normalize(), f32::MAX, 1.0)
+            }
+            LightKind::Spot { pos, .. } => {
+                let to_light = pos - point;
+                let d = to_light.magnitude();
+
+                if d <= 1e-6 {
+                    return None;
+                }
+
+                let l = to_light / d;
+
+                (l, d, self.falloff(d) * self.cone_factor(-l))
+            }
+            LightKind::Rect { corner, edge_u, edge_v } => {
+                let cross = edge_u.cross(edge_v);
+                let p = corner + edge_u * u.x + edge_v * u.y;
+
+                Light::area_sample(point, p, cross.normalize(), cross.magnitude())?
+            }
+            LightKind::Disk { center, normal, radius } => {
+                let n = normal.normalize();
+                let (t, b) = orthonormal_basis(n);
+                let d = sample_disk(u) * radius;
+                let p = center + t * d.x + b * d.y;
+
+                Light::area_sample(point, p, n, PI * radius * radius)?
+            }
+            LightKind::Sphere { center, radius } => {
+                // 지점에서 보이는 구의 원뿔 안에서 방향을 고름
+                let to_center = center - point;
+                let dc = to_center.magnitude();
+
+                if dc <= radius {
+                    return None;
+                }
+
+                let w = to_center / dc;
+                let sin_max = radius / dc;
+                let cos_max = (1.0 - sin_max * sin_max).max(0.0).sqrt();
+                let l = sample_cone(w, cos_max, u);
+
+                // 구 표면까지 거리
+                let cos_theta = l.dot(w);
+                let det = (radius * radius - dc * dc * (1.0 - cos_theta * cos_theta)).max(0.0);
+                let d = dc * cos_theta - det.sqrt();
+
+                // 원뿔의 입체각
+                (l, d, 2.0 * PI * (1.0 - cos_max))
+            }
+        };
+
+        if factor <= 0.0 {
+            return None;
+        }
+
+        Some(LightSample { dir, distance, radiance: self.color * (self.intensity * factor) })
+    }
+
+    // point 에서 dir 방향으로 distance 떨어진 조명 위의 점을 sample() 이 고를 확률 밀도 (입체각 기준)
+    // 크기가 없는 조명은 광선이 닿을 수 없으므로 0
+    pub fn pdf(&self, point: cgmath::Vector3<f32>, dir: cgmath::Vector3<f32>, distance: f32) -> f32 {
+        match self.kind {
+            LightKind::Rect { edge_u, edge_v, .. } => {
+                let cross = edge_u.cross(edge_v);
+                Light::area_pdf(dir, distance, cross.normalize(), cross.magnitude())
+            }
+            LightKind::Disk { normal, radius, .. } => {
+                Light::area_pdf(dir, distance, normal.normalize(), PI * radius * radius)
+            }
+            LightKind::Sphere { center, radius } => {
+                let dc = (center - point).magnitude();
+
+                if dc <= radius {
+                    return 0.0;
+                }
+
+                let sin_max = radius / dc;
+                let cos_max = (1.0 - sin_max * sin_max).max(0.0).sqrt();
+
+                1.0 / (2.0 * PI * (1.0 - cos_max))
+            }
+            _ => 0.0,
+        }
+    }
+
+    // 조명에서 나가는 빛 하나를 고름 (bidirectional path tracing 에서 조명 쪽 경로의 시작)
+    // 면적 조명은 표면 위의 균일한 점에서 cos 에 비례하는 방향, 점/spot 조명은 (원뿔 안의) 균일한 방향
+    // 무한히 먼 directional 조명은 시작점이 없으므로 None
+    pub fn sample_emission(&self, u_pos: cgmath::Vector2<f32>, u_dir: cgmath::Vector2<f32>) -> Option<EmissionSample> {
+        let (point, normal) = match self.kind {
+            LightKind::Point { pos } | LightKind::Spot { pos, .. } => (pos, None),
+            LightKind::Directional { .. } => return None,
+            LightKind::Rect { corner, edge_u, edge_v } => {
+                (corner + edge_u * u_pos.x + edge_v * u_pos.y, Some(edge_u.cross(edge_v).normalize()))
+            }
+            LightKind::Disk { center, normal, radius } => {
+                let n = normal.normalize();
+                let (t, b) = orthonormal_basis(n);
+                let d = sample_disk(u_pos) * radius;
+
+                (center + t * d.x + b * d.y, Some(n))
+            }
+            LightKind::Sphere { center, radius } => {
+                // 구 위의 균일한 점
+                let n = sample_cone(cgmath::vec3(0.0, 0.0, 1.0), -1.0, u_pos);
+                (center + n * radius, Some(n))
+            }
+        };
+
+        let dir = match (&self.kind, normal) {
+            (_, Some(n)) => sample_cosine_hemisphere(n, u_dir),
+            (LightKind::Spot { dir, cone_angle, .. }, None) => sample_cone(dir.normalize(), cone_angle.to_radians().cos(), u_dir),
+            _ => sample_cone(cgmath::vec3(0.0, 0.0, 1.0), -1.0, u_dir),
+        };
+
+        let pdf_dir = self.emission_direction_pdf(point, dir);
+        if pdf_dir <= 0.0 {
+            return None;
+        }
+
+        Some(EmissionSample {
+            point,
+            normal,
+            dir,
+            radiance: self.color * (self.intensity * self.cone_factor(dir)),
+            pdf_pos: self.emission_position_pdf(),
+            pdf_dir,
+        })
+    }
+
+    // sample_emission 이 조명 위의 한 점을 고를 확률 밀도 (면적 기준, 점 조명은 1)
+    pub fn emission_position_pdf(&self) -> f32 {
+        match self.kind {
+            LightKind::Rect { edge_u, edge_v, .. } => 1.0 / edge_u.cross(edge_v).magnitude(),
+            LightKind::Disk { radius, .. } => 1.0 / (PI * radius * radius),
+            LightKind::Sphere { radius, .. } => 1.0 / (4.0 * PI * radius * radius),
+            LightKind::Point { .. } | LightKind::Spot { .. } => 1.0,
+            LightKind::Directional { .. } => 0.0,
+        }
+    }
+
+    // sample_emission 이 조명 위의 point 에서 dir 방향을 고를 확률 밀도 (입체각 기준)
+    pub fn emission_direction_pdf(&self, point: cgmath::Vector3<f32>, dir: cgmath::Vector3<f32>) -> f32 {
+        match self.kind {
+            LightKind::Point { .. } => 1.0 / (4.0 * PI),
+            LightKind::Spot { dir: axis, cone_angle, .. } => {
+                let cos_outer = cone_angle.to_radians().cos();
+
+                if dir.dot(axis.normalize()) >= cos_outer {
+                    1.0 / (2.0 * PI * (1.0 - cos_outer).max(1e-6))
+                } else {
+                    0.0
+                }
+            }
+            LightKind::Directional { .. } => 0.0,
+            _ => self.normal_at(point).map_or(0.0, |n| n.dot(dir).max(0.0) / PI),
+        }
+    }
+
+    // 면적 조명 위의 point 에서 빛이 나가는 쪽의 법선 (크기가 없는 조명은 None)
+    pub fn normal_at(&self, point: cgmath::Vector3<f32>) -> Option<cgmath::Vector3<f32>> {
+        match self.kind {
+            LightKind::Rect { edge_u, edge_v, .. } => Some(edge_u.cross(edge_v).normalize()),
+            LightKind::Disk { normal, .. } => Some(normal.normalize()),
+            LightKind::Sphere { center, .. } => Some((point - center).normalize()),
+            _ => None,
+        }
+    }
+
+    // 점/spot 조명의 attenuation 감쇠를 물리적인 1 / d^2 과 비교한 비율 (면적 조명은 1)
+    // sample_emission 으로 시작한 경로가 처음 닿은 곳부터 곱하면 sample() 과 같은 밝기
+    pub fn falloff_scale(&self, d: f32) -> f32 {
+        match self.kind {
+            LightKind::Point { .. } | LightKind::Spot { .. } => self.falloff(d) * d * d,
+            _ => 1.0,
+        }
+    }
+
+    // spot 조명에서 dir (조명에서 나가는 방향) 으로 나가는 빛의 비율, 원뿔 축과의 각도로 결정 (다른 조명은 1)
+    fn cone_factor(&self, dir: cgmath::Vector3<f32>) -> f32 {
+        let LightKind::Spot { dir: axis, cone_angle, soft_edge, .. } = self.kind else {
+            return 1.0;
+        };
+
+        let cos_theta = dir.dot(axis.normalize());
+        let cos_outer = cone_angle.to_radians().cos();
+        let cos_inner = (cone_angle - soft_edge).max(0.0).to_radians().cos();
+
+        if cos_inner > cos_outer {
+            smoothstep(cos_outer, cos_inner, cos_theta)
+        } else if cos_theta >= cos_outer {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    // 면적 A 위에서 균일하게 고른 점의 확률 밀도를 입체각 기준으로 바꿈: d^2 / (cos(조명 쪽 각도) * A)
+    fn area_pdf(dir: cgmath::Vector3<f32>, distance: f32, n: cgmath::Vector3<f32>, area: f32) -> f32 {
+        let cos_light = (-dir).dot(n);
+
+        if cos_light <= 0.0 {
+            return 0.0;
+        }
+
+        distance * distance / (cos_light * area)
+    }
+
+    // 면적 A 인 조명 위의 점 p 를 균일하게 고른 경우: cos(조명 쪽 각도) * A / d^2
+    fn area_sample(
+        point: cgmath::Vector3<f32>,
+        p: cgmath::Vector3<f32>,
+        n: cgmath::Vector3<f32>,
+        area: f32,
+    ) -> Option<(cgmath::Vector3<f32>, f32, f32)> {
+        let to_light = p - point;
+        let d = to_light.magnitude();
+
+        if d <= 1e-6 {
+            return None;
+        }
+
+        let l = to_light / d;
+
+        let cos_light = (-l).dot(n);
+        if cos_light <= 0.0 {
+            return None;
+        }
+
+        Some((l, d, cos_light * area / (d * d)))
+    }
+
+    fn falloff(&self, d: f32) -> f32 {
+        let a = self.attenuation;
+        1.0 / (a.x + a.y * d + a.z * d * d).max(1e-6)
+    }
+
+    // 카메라 광선이 면적 조명에 직접 닿는 거리 (점 조명은 보이지 않음)
+    pub fn intersect(&self, ray: &Ray) -> Option<f32> {
+        match self.kind {
+            LightKind::Rect { corner, edge_u, edge_v } => {
+                let cross = edge_u.cross(edge_v);
+                let n = cross.normalize();
+                let d = Light::intersect_front_face(ray, corner, n)?;
+
+                let q = ray.start + ray.dir * d - corner;
+                let area = cross.magnitude();
+                let a = q.cross(edge_v).dot(n) / area;
+                let b = edge_u.cross(q).dot(n) / area;
+
+                ((0.0..=1.0).contains(&a) && (0.0..=1.0).contains(&b)).then_some(d)
+            }
+            LightKind::Disk { center, normal, radius } => {
+                let d = Light::intersect_front_face(ray, center, normal.normalize())?;
+                let p = ray.start + ray.dir * d;
+
+                ((p - center).magnitude2() <= radius * radius).then_some(d)
+            }
+            LightKind::Sphere { center, radius } => {
+                let oc = ray.start - center;
+                let b = ray.dir.dot(oc);
+                let c = oc.dot(oc) - radius * radius;
+                let det = b * b - c;
+
+                if det < 0.0 {
+                    return None;
+                }
+
+                let d1 = -b - det.sqrt();
+                let d2 = -b + det.sqrt();
+
+                if d1 >= 0.0 {
+                    Some(d1)
+                } else if d2 >= 0.0 {
+                    Some(d2)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // 조명을 직접 봤을 때의 색
+    pub fn emission(&self) -> cgmath::Vector3<f32> {
+        self.color * self.intensity
+    }
+
+    // 앞면(빛이 나가는 쪽)에서 들어오는 광선과 평면의 충돌 거리
+    fn intersect_front_face(ray: &Ray, p0: cgmath::Vector3<f32>, n: cgmath::Vector3<f32>) -> Option<f32> {
+        let deno = ray.dir.dot(n);
+        if deno >= -1e-6 {
+            return None;
+        }
+
+        let d = (p0 - ray.start).dot(n) / deno;
+        (d >= 0.0).then_some(d)
+    }
+}
+
+// lights 중 하나를 u 로 고름 (모두 같은 확률), lights 가 비어있으면 안 됨
+pub fn pick_light(lights: &[Light], u: f32) -> &Light {
+    &lights[((u * lights.len() as f32) as usize).min(lights.len() - 1)]
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
@@ -1,4 +1,5 @@
 use raytracing_rs::raytracer::Raytracer;
+use raytracing_rs::scene::Scene;
 use raytracing_rs::state::State;
 
 use winit::{
@@ -30,7 +31,15 @@ async fn run() {
     let mut img_buff = image::RgbImage::new(width as u32, height as u32);
 
     // Start ray tracing
-    let ray = Raytracer::new(width, height);
+    let scene_path = std::env::args().nth(1).unwrap_or_else(|| "./scenes/default.toml".to_string());
+    let scene = match Scene::load(&scene_path) {
+        Ok(scene) => scene,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            std::process::exit(1);
+        }
+    };
+    let ray = Raytracer::new(width, height, scene);
     ray.render(&mut img_buff);
 
     // initialize wgpu
@@ -44,52 +53,50 @@ async fn run() {
             Event::WindowEvent {
                 ref event,
                 window_id
-            } if window_id == state.window().id() => {
-                if !state.input(event) {
-                    match event {
-                        WindowEvent::CloseRequested | WindowEvent::KeyboardInput {
-                            event:
-                            KeyEvent {
-                                state: ElementState::Pressed,
-                                physical_key: PhysicalKey::Code(KeyCode::Escape),
-                                ..
-                            },
+            } if window_id == state.window().id() && !state.input(event) => {
+                match event {
+                    WindowEvent::CloseRequested | WindowEvent::KeyboardInput {
+                        event:
+                        KeyEvent {
+                            state: ElementState::Pressed,
+                            physical_key: PhysicalKey::Code(KeyCode::Escape),
                             ..
-                        } => elwt.exit(),
-                        WindowEvent::Resized(physical_size) => {
-                            // log::info!("physical_size: {physical_size:?}");
-                            // println!("physical_size: {physical_size:?}");
-                            surface_configured = true;
-                            state.resize(*physical_size);
                         },
-                        WindowEvent::RedrawRequested => {
-                            // This tells winit that we want another frame after this one
-                            state.window().request_redraw();
+                        ..
+                    } => elwt.exit(),
+                    WindowEvent::Resized(physical_size) => {
+                        // log::info!("physical_size: {physical_size:?}");
+                        // println!("physical_size: {physical_size:?}");
+                        surface_configured = true;
+                        state.resize(*physical_size);
+                    },
+                    WindowEvent::RedrawRequested => {
+                        // This tells winit that we want another frame after this one
+                        state.window().request_redraw();
+
+                        if !surface_configured {
+                            return;
+                        }
 
-                            if !surface_configured {
-                                return;
+                        match state.render() {
+                            Ok(_) => {}
+                            // Reconfigure the surface if it's lost or outdated
+                            Err(
+                                wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated,
+                            ) => state.resize(state.size),
+                            // The system is out of memory, we should probably quit
+                            Err(wgpu::SurfaceError::OutOfMemory) => {
+                                log::error!("OutOfMemory");
+                                elwt.exit();
                             }
 
-                            match state.render() {
-                                Ok(_) => {}
-                                // Reconfigure the surface if it's lost or outdated
-                                Err(
-                                    wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated,
-                                ) => state.resize(state.size),
-                                // The system is out of memory, we should probably quit
-                                Err(wgpu::SurfaceError::OutOfMemory) => {
-                                    log::error!("OutOfMemory");
-                                    elwt.exit();
-                                }
-
-                                // This happens when the frame takes too long to present
-                                Err(wgpu::SurfaceError::Timeout) => {
-                                    log::warn!("Surface timeout")
-                                }
+                            // This happens when the frame takes too long to present
+                            Err(wgpu::SurfaceError::Timeout) => {
+                                log::warn!("Surface timeout")
                             }
                         }
-                        _ => {}
                     }
+                    _ => {}
                 }
             }
 
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use cgmath::ElementWise;
+
+use crate::microfacet::MetallicRoughness;
+use crate::optics::Fresnel;
+use crate::texture::Texture;
+
+// 물체 표면의 색과 광학적 성질
+// 여러 물체가 Arc 로 같은 재질을 공유할 수 있음 (메쉬의 삼각형들, 장면 파일의 [materials.이름])
+#[derive(Debug, Clone)]
+pub struct Material {
+    // 조명에 대한 표면의 반응 (amb 는 두 모델 모두 더함)
+    pub shading: Shading,
+
+    // Phong 모델
+    pub amb: cgmath::Vector3<f32>, // ambient
+    pub diff: cgmath::Vector3<f32>, // diffuse
+    pub spec: cgmath::Vector3<f32>, // specular
+    pub ks: f32,
+    pub alpha: f32,
+
+    // 있으면 amb, diff 에 텍스처 색을 곱함
+    pub amb_tex: Option<Arc<Texture>>,
+    pub dif_tex: Option<Arc<Texture>>,
+
+    pub reflection: f32, // 0 ~ 1, 거울 반사 비율
+    pub transparency: f32, // 0 ~ 1, 경계면에서 fresnel 에 따라 반사/굴절되는 비율
+
+    // 0 이면 거울 반사와 선명한 굴절, 클수록 반사/굴절이 흐려짐 (brushed metal, 불투명 유리)
+    pub roughness: f32,
+
+    pub ior: f32, // index of refraction (유리 1.5, 물 1.33)
+    pub fresnel: Fresnel,
+
+    // 투명한 물체끼리 겹친 공간은 priority 가 높은 물체의 재질 (MediumStack)
+    pub priority: u32,
+
+    // 안을 지나는 빛이 단위 거리마다 흡수되는 정도 (R, G, B), 투과율 exp(-absorption * 거리) (Beer-Lambert)
+    // 두꺼운 유리일수록 어둡고 진한 색이 됨
+    pub absorption: cgmath::Vector3<f32>,
+}
+
+// 물체마다 고를 수 있는 shading 모델
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Shading {
+    // diff, spec, alpha 를 쓰는 Phong
+    #[default]
+    Phong,
+    // GGX 미세면 모델 (dif_tex 는 base_color 에 곱함)
+    MetallicRoughness(MetallicRoughness),
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Material {
+            shading: Shading::default(),
+            amb: cgmath::vec3(0.0, 0.0, 0.0),
+            diff: cgmath::vec3(0.0, 0.0, 0.0),
+            spec: cgmath::vec3(0.0, 0.0, 0.0),
+            ks: 0.0,
+            alpha: 1.0,
+            amb_tex: None,
+            dif_tex: None,
+            reflection: 0.0,
+            transparency: 0.0,
+            roughness: 0.0,
+            ior: 1.5,
+            fresnel: Fresnel::default(),
+            priority: 0,
+            absorption: cgmath::vec3(0.0, 0.0, 0.0),
+        }
+    }
+}
+
+impl Material {
+    // 텍스처 좌표 uv 에서의 ambient 색
+    pub fn ambient(&self, uv: &cgmath::Vector2<f32>) -> cgmath::Vector3<f32> {
+        match &self.amb_tex {
+            Some(texture) => self.amb.mul_element_wise(texture.get_sample_linear(uv)),
+            None => self.amb,
+        }
+    }
+
+    // 텍스처 좌표 uv 에서 diffuse 에 곱할 색 (텍스처가 없으면 1)
+    pub fn diffuse_texture(&self, uv: &cgmath::Vector2<f32>) -> cgmath::Vector3<f32> {
+        match &self.dif_tex {
+            Some(texture) => texture.get_sample_linear(uv),
+            None => cgmath::vec3(1.0, 1.0, 1.0),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::material::Material;
+
+// 광선이 지금 지나가고 있는 투명한 물체들 (nested dielectrics)
+// 물체가 겹치면 priority 가 높은 물체의 재질이 그 공간을 차지함
+// 예: 유리컵 (priority 높음) 과 살짝 겹치게 만든 물, 유리 안의 공기 방울
+// https://www.researchgate.net/publication/247523037_Simple_Nested_Dielectrics_in_Ray_Traced_Images
+#[derive(Clone, Default)]
+pub struct MediumStack<'a> {
+    media: Vec<&'a Material>,
+}
+
+impl<'a> MediumStack<'a> {
+    pub fn new() -> Self {
+        MediumStack { media: Vec::new() }
+    }
+
+    // 지금 광선이 있는 매질: priority 가 가장 높은 것 (같으면 나중에 들어간 것), 없으면 공기
+    pub fn current(&self) -> Option<&'a Material> {
+        self.media.iter().copied().max_by_key(|m| m.priority)
+    }
+
+    pub fn ior(&self) -> f32 {
+        self.current().map_or(1.0, |m| m.ior)
+    }
+
+    // 지금 매질 안에서 distance 만큼 지나간 빛이 흡수되지 않고 남는 비율
+    pub fn transmittance(&self, distance: f32) -> cgmath::Vector3<f32> {
+        match self.current() {
+            Some(m) => m.absorption.map(|a| (-a * distance).exp()),
+            None => cgmath::vec3(1.0, 1.0, 1.0),
+        }
+    }
+
+    // material 의 경계면이 실제로 보이는지
+    // 더 높은 priority 의 물체 안에 있으면 경계면은 무시하고 광선이 그대로 지나감
+    pub fn is_boundary_visible(&self, material: &Material, entering: bool) -> bool {
+        match self.current() {
+            Some(current) if entering => material.priority >= current.priority,
+            Some(current) => std::ptr::eq(current, material) || material.priority >= current.priority,
+            None => true,
+        }
+    }
+
+    // material 안으로 들어간 뒤의 상태
+    pub fn enter(&self, material: &'a Material) -> Self {
+        let mut media = self.media.clone();
+        media.push(material);
+        MediumStack { media }
+    }
+
+    // material 밖으로 나간 뒤의 상태 (카메라가 물체 안에서 시작하면 없을 수도 있음)
+    pub fn exit(&self, material: &Material) -> Self {
+        let mut media = self.media.clone();
+
+        if let Some(i) = media.iter().rposition(|m| std::ptr::eq(*m, material)) {
+            media.remove(i);
+        }
+
+        MediumStack { media }
+    }
+}
@@ -0,0 +1,157 @@
+use std::f32::consts::PI;
+
+use cgmath::{ElementWise, InnerSpace};
+
+use crate::sampling::orthonormal_basis;
+
+// metallic-roughness 재질 (glTF, Unreal, Blender Principled 와 같은 방식)
+// 미세면 (microfacet) 분포는 GGX, 가려짐은 Smith, 반사율은 Schlick Fresnel
+// https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#appendix-b-brdf-implementation
+// https://media.disneyanimation.com/uploads/production/publication_asset/48/asset/s2012_pbs_disney_brdf_notes_v3.pdf
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetallicRoughness {
+    pub base_color: cgmath::Vector3<f32>, // 유전체는 diffuse 색, 금속은 반사되는 색
+    pub metallic: f32, // 0 (유전체) ~ 1 (금속)
+    pub roughness: f32, // 0 (매끈함) ~ 1 (거침)
+    pub specular: f32, // 유전체의 정면 반사율, 0.5 가 F0 = 0.04 (굴절률 1.5)
+}
+
+impl Default for MetallicRoughness {
+    fn default() -> Self {
+        MetallicRoughness {
+            base_color: cgmath::vec3(0.8, 0.8, 0.8),
+            metallic: 0.0,
+            roughness: 0.5,
+            specular: 0.5,
+        }
+    }
+}
+
+// roughness 0 은 점 조명의 하이라이트가 보이지 않으므로 최소값을 둠
+const MIN_ALPHA: f32 = 1e-3;
+
+// GGX 의 alpha (roughness 를 제곱해야 roughness 변화가 눈에 고르게 보임)
+pub fn roughness_to_alpha(roughness: f32) -> f32 {
+    (roughness * roughness).max(MIN_ALPHA)
+}
+
+impl MetallicRoughness {
+    pub fn alpha(&self) -> f32 {
+        roughness_to_alpha(self.roughness)
+    }
+
+    // 정면 (cos = 1) 에서의 반사율, 금속은 base_color 를 반사
+    pub fn f0(&self, base_color: cgmath::Vector3<f32>) -> cgmath::Vector3<f32> {
+        let dielectric = 0.08 * self.specular;
+        cgmath::vec3(dielectric, dielectric, dielectric) * (1.0 - self.metallic) + base_color * self.metallic
+    }
+
+    // n: 법선, v: 보는 방향, l: 조명 방향 (모두 표면에서 나가는 쪽 단위 벡터)
+    // tint: base_color 에 곱할 텍스처 색
+    // BRDF * cos(n, l) * PI 를 돌려줌
+    // Phong 의 diff 는 Lambert 의 1 / PI 가 빠진 값이므로, 같은 조명에서 흰 유전체가 diff = 1 인 Phong 과 같은 밝기가 되도록 PI 를 곱함
+    pub fn eval(
+        &self,
+        n: cgmath::Vector3<f32>,
+        v: cgmath::Vector3<f32>,
+        l: cgmath::Vector3<f32>,
+        tint: cgmath::Vector3<f32>,
+    ) -> cgmath::Vector3<f32> {
+        let n_dot_l = n.dot(l);
+
+        if n_dot_l <= 0.0 {
+            return cgmath::vec3(0.0, 0.0, 0.0);
+        }
+
+        // 보간된 법선 때문에 살짝 뒤에서 볼 수도 있음
+        let n_dot_v = n.dot(v).max(1e-4);
+
+        let h = (v + l).normalize();
+        let n_dot_h = n.dot(h).max(0.0);
+        let v_dot_h = v.dot(h).max(0.0);
+
+        let base_color = self.base_color.mul_element_wise(tint);
+        let alpha = self.alpha();
+
+        let f0 = self.f0(base_color);
+        let f = fresnel_schlick_color(f0, v_dot_h);
+        let specular = f * (ggx_distribution(n_dot_h, alpha) * smith_visibility(n_dot_v, n_dot_l, alpha));
+
+        // 표면에서 반사되지 않고 들어간 빛만 diffuse 로 나옴, 금속은 diffuse 가 없음
+        // h 기준 fresnel 을 쓰면 비스듬히 볼 때 반사된 양보다 적게 빠져서 에너지가 늘어나므로 n.v 기준으로 뺌
+        let entered = cgmath::vec3(1.0, 1.0, 1.0) - fresnel_schlick_color(f0, n_dot_v);
+        let diffuse = entered.mul_element_wise(base_color) * ((1.0 - self.metallic) / PI);
+
+        (diffuse + specular) * (n_dot_l * PI)
+    }
+}
+
+// 법선과 h 사이의 각에 따른 미세면의 분포 D
+pub fn ggx_distribution(n_dot_h: f32, alpha: f32) -> f32 {
+    let a2 = alpha * alpha;
+    let d = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+
+    a2 / (PI * d * d)
+}
+
+// height-correlated Smith 가려짐 G 를 4 (n.v) (n.l) 로 나눈 값
+// https://jcgt.org/published/0003/02/03/paper.pdf
+pub fn smith_visibility(n_dot_v: f32, n_dot_l: f32, alpha: f32) -> f32 {
+    let a2 = alpha * alpha;
+    let ggx_v = n_dot_l * (n_dot_v * n_dot_v * (1.0 - a2) + a2).sqrt();
+    let ggx_l = n_dot_v * (n_dot_l * n_dot_l * (1.0 - a2) + a2).sqrt();
+
+    0.5 / (ggx_v + ggx_l)
+}
+
+pub fn fresnel_schlick_color(f0: cgmath::Vector3<f32>, cos: f32) -> cgmath::Vector3<f32> {
+    let k = (1.0 - cos.clamp(0.0, 1.0)).powi(5);
+    f0 + (cgmath::vec3(1.0, 1.0, 1.0) - f0) * k
+}
+
+// 방향 w 가 미세면들에 가려지지 않고 보이는 비율 (Smith G1)
+pub fn smith_g1(n_dot_w: f32, alpha: f32) -> f32 {
+    let a2 = alpha * alpha;
+    let n_dot_w = n_dot_w.clamp(0.0, 1.0);
+
+    2.0 * n_dot_w / (n_dot_w + (a2 + (1.0 - a2) * n_dot_w * n_dot_w).sqrt())
+}
+
+// sample_visible_normal 로 고른 방향 l 에 곱할 무게 G2(v, l) / G1(v) (D 와 cos 항은 확률 밀도와 약분됨)
+pub fn sampled_weight(n_dot_v: f32, n_dot_l: f32, alpha: f32) -> f32 {
+    let n_dot_v = n_dot_v.max(1e-4);
+    let g2 = 4.0 * n_dot_v * n_dot_l * smith_visibility(n_dot_v, n_dot_l, alpha);
+
+    g2 / smith_g1(n_dot_v, alpha)
+}
+
+// 법선 n 인 면을 v 방향에서 볼 때 보이는 미세면의 법선을 GGX 분포에 따라 고름 (visible normal sampling)
+// 이 법선으로 반사/굴절시킨 방향은 D 가 큰 방향에 몰림
+// https://jcgt.org/published/0007/04/01/paper.pdf
+pub fn sample_visible_normal(
+    n: cgmath::Vector3<f32>,
+    v: cgmath::Vector3<f32>,
+    alpha: f32,
+    u: cgmath::Vector2<f32>,
+) -> cgmath::Vector3<f32> {
+    // n 이 z 축인 좌표계로 옮기고, alpha = 1 이 되도록 늘림
+    let (t, b) = orthonormal_basis(n);
+    let vh = cgmath::vec3(alpha * v.dot(t), alpha * v.dot(b), v.dot(n).max(1e-4)).normalize();
+
+    let len2 = vh.x * vh.x + vh.y * vh.y;
+    let t1 = if len2 > 0.0 { cgmath::vec3(-vh.y, vh.x, 0.0) / len2.sqrt() } else { cgmath::vec3(1.0, 0.0, 0.0) };
+    let t2 = vh.cross(t1);
+
+    // vh 에 수직인 원판 위의 점, 보이지 않는 쪽 절반은 줄여서 투영
+    let r = u.x.sqrt();
+    let phi = 2.0 * PI * u.y;
+    let p1 = r * phi.cos();
+    let s = 0.5 * (1.0 + vh.z);
+    let p2 = (1.0 - s) * (1.0 - p1 * p1).sqrt() + s * r * phi.sin();
+
+    let nh = t1 * p1 + t2 * p2 + vh * (1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt();
+
+    // 다시 줄여서 원래 좌표계로
+    let m = cgmath::vec3(alpha * nh.x, alpha * nh.y, nh.z.max(0.0)).normalize();
+    t * m.x + b * m.y + n * m.z
+}
@@ -0,0 +1,58 @@
+use cgmath::InnerSpace;
+
+use crate::integrator::Integrator;
+use crate::ray::Ray;
+use crate::raytracer::Raytracer;
+use crate::sampler::SampleStream;
+use crate::sampling::sample_cosine_hemisphere;
+
+// ambient occlusion: 처음 닿은 곳에서 반구 방향으로 광선을 쏴서 distance 안에 가리는 물체가 없는 비율
+// 흰색은 트인 곳, 구석이나 틈일수록 어두움 (재질, 조명과 상관없이 형태만 볼 때)
+// Whitted::ambient_occlusion 으로 amb 에 곱할 수도 있음
+#[derive(Debug, Clone, Copy)]
+pub struct AmbientOcclusion {
+    pub samples: u32, // 한 지점에서 쏘는 광선 개수
+    pub distance: f32, // 이보다 먼 물체는 가리지 않는 것으로 봄
+}
+
+impl Default for AmbientOcclusion {
+    fn default() -> Self {
+        AmbientOcclusion { samples: 16, distance: 1.0 }
+    }
+}
+
+impl AmbientOcclusion {
+    // point 에서 n 쪽 반구가 트인 비율 [0, 1]
+    pub fn estimate(&self, raytracer: &Raytracer, point: cgmath::Vector3<f32>, n: cgmath::Vector3<f32>, samples: &mut SampleStream) -> f32 {
+        // cos 에 비례해서 방향을 고르므로 그냥 센 비율이 cos 가중 평균
+        let n_samples = self.samples.max(1);
+        let mut visible = 0;
+
+        for _ in 0..n_samples {
+            let dir = sample_cosine_hemisphere(n, samples.next_2d());
+            let occlusion_ray = Ray { start: point + (dir * 1e-4f32), dir };
+
+            if !raytracer.find_any_collision(&occlusion_ray, self.distance) {
+                visible += 1;
+            }
+        }
+
+        visible as f32 / n_samples as f32
+    }
+}
+
+impl Integrator for AmbientOcclusion {
+    fn radiance(&self, raytracer: &Raytracer, ray: &Ray, samples: &mut SampleStream) -> cgmath::Vector3<f32> {
+        let hit = raytracer.find_closest_collision(ray);
+
+        if hit.d < 0.0 {
+            return cgmath::vec3(0.0, 0.0, 0.0);
+        }
+
+        // 보는 쪽을 향한 법선
+        let n = if ray.dir.dot(hit.normal) < 0.0 { hit.normal } else { -1.0 * hit.normal }.normalize();
+
+        let v = self.estimate(raytracer, hit.point, n, samples);
+        cgmath::vec3(v, v, v)
+    }
+}
@@ -0,0 +1,90 @@
+use std::str::FromStr;
+
+use cgmath::InnerSpace;
+
+// 유전체 (유리, 물) 경계면에서 반사되는 빛의 비율을 계산하는 방법
+// 나머지 (1 - 반사율) 는 굴절되어 들어감
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fresnel {
+    // 편광을 고려한 정확한 식
+    #[default]
+    Exact,
+    // Schlick 근사 R0 + (1 - R0)(1 - cos)^5
+    Schlick,
+}
+
+impl Fresnel {
+    // cos_i: 입사각의 cos (법선은 입사하는 쪽), eta: 나가는 쪽 / 들어오는 쪽 굴절률
+    // 전반사 (total internal reflection) 면 1
+    pub fn reflectance(&self, cos_i: f32, eta: f32) -> f32 {
+        match self {
+            Fresnel::Exact => fresnel_dielectric(cos_i, eta),
+            Fresnel::Schlick => fresnel_schlick(cos_i, eta),
+        }
+    }
+}
+
+impl FromStr for Fresnel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exact" => Ok(Fresnel::Exact),
+            "schlick" => Ok(Fresnel::Schlick),
+            _ => Err(anyhow::anyhow!("unknown fresnel '{}', expected exact or schlick", s)),
+        }
+    }
+}
+
+// https://pbr-book.org/3ed-2018/Reflection_Models/Specular_Reflection_and_Transmission
+pub fn fresnel_dielectric(cos_i: f32, eta: f32) -> f32 {
+    let cos_i = cos_i.clamp(0.0, 1.0);
+    let sin_t2 = (1.0 - cos_i * cos_i) / (eta * eta);
+
+    if sin_t2 >= 1.0 {
+        return 1.0;
+    }
+
+    let cos_t = (1.0 - sin_t2).sqrt();
+
+    let r_parl = (eta * cos_i - cos_t) / (eta * cos_i + cos_t);
+    let r_perp = (cos_i - eta * cos_t) / (cos_i + eta * cos_t);
+
+    (r_parl * r_parl + r_perp * r_perp) * 0.5
+}
+
+pub fn fresnel_schlick(cos_i: f32, eta: f32) -> f32 {
+    let cos_i = cos_i.clamp(0.0, 1.0);
+    let sin_t2 = (1.0 - cos_i * cos_i) / (eta * eta);
+
+    if sin_t2 >= 1.0 {
+        return 1.0;
+    }
+
+    // 굴절률이 작아지는 쪽으로 나갈 때는 굴절각 기준으로 계산해야 전반사 근처에서 맞음
+    let cos = if eta < 1.0 { (1.0 - sin_t2).sqrt() } else { cos_i };
+
+    let r0 = ((eta - 1.0) / (eta + 1.0)).powi(2);
+    r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+}
+
+// n 에 대해 dir 을 거울 반사
+pub fn reflect(dir: cgmath::Vector3<f32>, n: cgmath::Vector3<f32>) -> cgmath::Vector3<f32> {
+    (dir - n * (2.0 * dir.dot(n))).normalize()
+}
+
+// 법선 n (dir 과 반대쪽, 입사하는 쪽) 인 면에서 굴절된 방향, 전반사면 None
+// eta: 나가는 쪽 / 들어오는 쪽 굴절률
+// https://web.cse.ohio-state.edu/~shen.94/681/Site/Slides_files/reflection_refraction.pdf
+pub fn refract(dir: cgmath::Vector3<f32>, n: cgmath::Vector3<f32>, eta: f32) -> Option<cgmath::Vector3<f32>> {
+    let cos_i = -n.dot(dir);
+    let sin_t2 = (1.0 - cos_i * cos_i) / (eta * eta);
+
+    if sin_t2 >= 1.0 {
+        return None;
+    }
+
+    let cos_t = (1.0 - sin_t2).sqrt();
+
+    Some((dir / eta + n * (cos_i / eta - cos_t)).normalize())
+}
@@ -0,0 +1,352 @@
+use cgmath::{ElementWise, InnerSpace};
+
+use crate::bsdf::SurfaceBsdf;
+use crate::hit::Hit;
+use crate::integrator::Integrator;
+use crate::light::Light;
+use crate::material::Material;
+use crate::medium::MediumStack;
+use crate::microfacet;
+use crate::optics;
+use crate::ray::Ray;
+use crate::raytracer::Raytracer;
+use crate::sampler::SampleStream;
+
+// Monte Carlo path tracing
+// 카메라에서 시작한 경로를 표면마다 한 방향으로 이어가면서 간접광 (color bleeding, caustics) 까지 계산
+// 샘플 수를 늘릴수록 정답으로 수렴
+// https://pbr-book.org/3ed-2018/Light_Transport_I_Surface_Reflection/Path_Tracing
+//
+// - 표면마다 조명을 직접 샘플링 (next event estimation) 하고, BSDF 로 고른 방향이 면적 조명에 닿은 경우와
+//   multiple importance sampling (power heuristic) 으로 합침
+// - amb 는 표면이 스스로 내는 빛으로 취급
+// - 조명으로 가는 그림자 광선은 투명한 물체도 막음 (유리를 지난 빛은 경로가 직접 조명에 닿아야 보이는 caustic)
+#[derive(Debug, Clone, Copy)]
+pub struct PathTracing {
+    pub max_depth: u32, // 경로가 튕기는 최대 횟수
+    pub rr_depth: u32, // 이만큼 튕긴 뒤부터 Russian roulette 로 경로를 확률적으로 끊음
+}
+
+impl Default for PathTracing {
+    fn default() -> Self {
+        PathTracing { max_depth: 16, rr_depth: 3 }
+    }
+}
+
+impl Integrator for PathTracing {
+    fn radiance(&self, raytracer: &Raytracer, ray: &Ray, samples: &mut SampleStream) -> cgmath::Vector3<f32> {
+        trace(raytracer, ray, samples, self.max_depth, self.rr_depth, true)
+    }
+}
+
+// 표면이 조명에서 직접 받은 빛만 계산 (path tracing 에서 간접광을 뺀 것)
+// 거울 반사와 유리는 path tracing 과 같이 따라감
+#[derive(Debug, Clone, Copy)]
+pub struct DirectLighting {
+    pub max_depth: u32, // 거울/유리를 따라가는 최대 횟수
+}
+
+impl Default for DirectLighting {
+    fn default() -> Self {
+        DirectLighting { max_depth: 16 }
+    }
+}
+
+impl Integrator for DirectLighting {
+    fn radiance(&self, raytracer: &Raytracer, ray: &Ray, samples: &mut SampleStream) -> cgmath::Vector3<f32> {
+        trace(raytracer, ray, samples, self.max_depth, self.max_depth, false)
+    }
+}
+
+// 카메라 광선 ray 를 따라 들어오는 빛
+// indirect 가 false 면 표면 BSDF 로 고른 방향은 조명에 닿는지만 보고 끝냄
+fn trace<'a>(
+    raytracer: &'a Raytracer,
+    ray: &Ray,
+    samples: &mut SampleStream,
+    max_depth: u32,
+    rr_depth: u32,
+    indirect: bool,
+) -> cgmath::Vector3<f32> {
+    let mut radiance = cgmath::vec3(0.0, 0.0, 0.0);
+    let mut throughput = cgmath::vec3(1.0, 1.0, 1.0);
+
+    let mut ray = Ray { start: ray.start, dir: ray.dir };
+    let mut media = MediumStack::<'a>::new();
+
+    // 마지막으로 튕긴 곳과, 그곳에서 표면 BSDF 로 방향을 고른 확률 밀도 (거울, 유리처럼 조명 샘플링을 안 했으면 None)
+    let mut vertex = ray.start;
+    let mut bsdf_pdf: Option<f32> = None;
+
+    // indirect 가 false 일 때 표면 BSDF 로 방향을 고른 뒤 (조명에 닿았는지만 확인)
+    let mut light_only = false;
+
+    let mut depth = 0;
+
+    loop {
+        // 보이지 않는 경계면은 튕긴 횟수에 넣지 않고 지나감
+        let next = next_visible_hit(raytracer, ray, media, true);
+        let hit = next.hit;
+        ray = next.ray;
+        media = next.media;
+
+        // 면적 조명에 닿으면 경로가 끝남
+        if let Some((d, light)) = next.light {
+            let weight = match bsdf_pdf {
+                Some(pdf) => {
+                    let p = ray.start + ray.dir * d;
+                    power_heuristic(pdf, light.pdf(vertex, ray.dir, (p - vertex).magnitude()))
+                }
+                None => 1.0,
+            };
+
+            let emission = light.emission().mul_element_wise(next.transmittance);
+            radiance += throughput.mul_element_wise(emission) * weight;
+            break;
+        }
+
+        if hit.d < 0.0 || light_only {
+            break;
+        }
+
+        let Some(material) = hit.material else {
+            break;
+        };
+
+        throughput = throughput.mul_element_wise(next.transmittance);
+
+        let entering = ray.dir.dot(hit.normal) < 0.0;
+        let normal = if entering { hit.normal } else { -1.0 * hit.normal }.normalize();
+        let v = -ray.dir;
+
+        let surface = 1.0 - material.reflection - material.transparency;
+        radiance += throughput.mul_element_wise(material.ambient(&hit.uv)) * surface;
+
+        if depth >= max_depth {
+            break;
+        }
+
+        // 표면 / 거울 반사 / 유리 중 하나를 비율대로 골라서 이어감 (고를 확률과 비율이 같으므로 throughput 은 그대로)
+        let lobe = select_lobe(material, samples.next_1d());
+
+        let dir = if lobe == Lobe::Surface {
+            let bsdf = SurfaceBsdf::new(material, normal, material.diffuse_texture(&hit.uv));
+
+            let direct = sample_lights(raytracer, &bsdf, hit.point, v, &media, samples);
+            radiance += throughput.mul_element_wise(direct);
+
+            let Some(sample) = bsdf.sample(v, samples.next_1d(), samples.next_2d()) else {
+                break;
+            };
+
+            throughput = throughput.mul_element_wise(sample.weight);
+            bsdf_pdf = Some(sample.pdf);
+            light_only = !indirect;
+            sample.dir
+        } else {
+            bsdf_pdf = None;
+
+            let Some((dir, weight, inner)) = sample_specular(material, &media, ray.dir, normal, entering, lobe == Lobe::Glass, samples) else {
+                break;
+            };
+
+            throughput *= weight;
+            media = inner;
+            dir
+        };
+
+        vertex = hit.point;
+        ray = Ray { start: hit.point + (dir * 1e-4f32), dir };
+        depth += 1;
+
+        // 기여가 작은 경로는 확률적으로 끊고, 살아남은 경로는 그만큼 키움
+        if depth > rr_depth {
+            let survive = throughput.x.max(throughput.y).max(throughput.z).min(0.95);
+
+            if survive <= 0.0 || samples.next_1d() >= survive {
+                break;
+            }
+
+            throughput /= survive;
+        }
+    }
+
+    radiance
+}
+
+// next_visible_hit 로 따라간 결과
+pub struct NextHit<'a> {
+    pub ray: Ray, // 마지막으로 따라간 광선 (경계면을 지날 때마다 시작점만 옮겨짐), hit.d 와 조명까지의 거리는 이 광선 기준
+    pub hit: Hit<'a>, // 처음 보이는 표면 (d < 0 이면 없음)
+    pub light: Option<(f32, &'a Light)>, // 표면보다 가까이 있는 면적 조명 (find_lights 가 true 일 때만)
+    pub media: MediumStack<'a>, // 표면 (또는 조명) 바로 앞의 매질
+    pub transmittance: cgmath::Vector3<f32>, // 표면 (또는 조명) 까지 지나온 매질의 투과율
+    pub distance: f32, // 처음 광선의 시작점부터 지나온 거리
+}
+
+// ray 를 따라 처음 보이는 표면까지 감
+// 더 높은 priority 의 투명한 물체 안에 있는 경계면은 없는 것처럼 지나가면서 media 만 바꿈
+pub fn next_visible_hit<'a>(raytracer: &'a Raytracer, mut ray: Ray, mut media: MediumStack<'a>, find_lights: bool) -> NextHit<'a> {
+    let mut transmittance = cgmath::vec3(1.0, 1.0, 1.0);
+    let mut distance = 0.0;
+
+    loop {
+        let hit = raytracer.find_closest_collision(&ray);
+
+        let light = if find_lights { raytracer.find_light(&ray, if hit.d >= 0.0 { hit.d } else { f32::MAX }) } else { None };
+        if let Some((d, _)) = light {
+            transmittance = transmittance.mul_element_wise(media.transmittance(d));
+            return NextHit { ray, hit, light, media, transmittance, distance: distance + d };
+        }
+
+        if hit.d < 0.0 {
+            return NextHit { ray, hit, light: None, media, transmittance, distance };
+        }
+
+        transmittance = transmittance.mul_element_wise(media.transmittance(hit.d));
+        distance += hit.d;
+
+        let entering = ray.dir.dot(hit.normal) < 0.0;
+        let visible = hit.material.is_none_or(|material| material.transparency <= 0.0 || media.is_boundary_visible(material, entering));
+
+        if visible {
+            return NextHit { ray, hit, light: None, media, transmittance, distance };
+        }
+
+        if let Some(material) = hit.material {
+            media = if entering { media.enter(material) } else { media.exit(material) };
+        }
+        ray = Ray { start: hit.point + (ray.dir * 1e-4f32), dir: ray.dir };
+    }
+}
+
+// 표면에서 경로를 이어가는 방법
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lobe {
+    Surface, // 표면의 BSDF
+    Mirror, // 거울 반사
+    Glass, // 유리 (fresnel 에 따라 반사 또는 굴절)
+}
+
+// 표면 (1 - reflection - transparency) / 거울 반사 (reflection) / 유리 (transparency) 중 하나를 u 로 비율대로 고름
+pub fn select_lobe(material: &Material, u: f32) -> Lobe {
+    let surface = 1.0 - material.reflection - material.transparency;
+
+    if u < surface {
+        Lobe::Surface
+    } else if u < surface + material.reflection {
+        Lobe::Mirror
+    } else {
+        Lobe::Glass
+    }
+}
+
+// 거울 반사 (refract 가 false) 또는 유리에서 이어갈 방향 (roughness 가 있으면 미세면 법선을 골라서 흐리게)
+// 유리는 fresnel 확률로 반사와 굴절 중 하나 (전반사면 fresnel 이 1)
+// (방향, throughput 에 곱할 값, 그 방향으로 가는 광선이 지나갈 매질), 미세면에 막히면 None
+pub fn sample_specular<'a>(
+    material: &'a Material,
+    media: &MediumStack<'a>,
+    ray_dir: cgmath::Vector3<f32>,
+    normal: cgmath::Vector3<f32>,
+    entering: bool,
+    refract: bool,
+    samples: &mut SampleStream,
+) -> Option<(cgmath::Vector3<f32>, f32, MediumStack<'a>)> {
+    let v = -ray_dir;
+
+    let glossy = material.roughness > 0.0;
+    let alpha = microfacet::roughness_to_alpha(material.roughness);
+
+    let m = if glossy {
+        microfacet::sample_visible_normal(normal, v, alpha, samples.next_2d())
+    } else {
+        normal
+    };
+
+    let mut dir = optics::reflect(ray_dir, m);
+    let mut transmitted = None;
+
+    if refract {
+        let (eta, inner) = if entering {
+            (material.ior / media.ior(), media.enter(material))
+        } else {
+            let inner = media.exit(material);
+            (inner.ior() / material.ior, inner)
+        };
+
+        let fresnel = material.fresnel.reflectance(v.dot(m), eta);
+
+        if samples.next_1d() >= fresnel {
+            if let Some(refracted) = optics::refract(ray_dir, m, eta) {
+                dir = refracted;
+                transmitted = Some(inner);
+            }
+        }
+    }
+
+    // 미세면에서 나간 방향이 반대쪽 면을 향하면 다른 미세면에 막힘
+    let cos_out = if transmitted.is_some() { -dir.dot(normal) } else { dir.dot(normal) };
+
+    if cos_out <= 0.0 {
+        return None;
+    }
+
+    let weight = if glossy { microfacet::sampled_weight(v.dot(normal), cos_out, alpha) } else { 1.0 };
+
+    Some((dir, weight, transmitted.unwrap_or_else(|| media.clone())))
+}
+
+// 모든 조명에서 한 점씩 골라 직접 조명을 더함 (next event estimation)
+fn sample_lights(
+    raytracer: &Raytracer,
+    bsdf: &SurfaceBsdf,
+    point: cgmath::Vector3<f32>,
+    v: cgmath::Vector3<f32>,
+    media: &MediumStack,
+    samples: &mut SampleStream,
+) -> cgmath::Vector3<f32> {
+    let mut direct = cgmath::vec3(0.0, 0.0, 0.0);
+
+    for light in raytracer.lights() {
+        let Some(light_sample) = light.sample(point, samples.next_2d()) else {
+            continue;
+        };
+        let l = light_sample.dir;
+
+        let f = bsdf.eval(v, l);
+        if f == cgmath::vec3(0.0, 0.0, 0.0) {
+            continue;
+        }
+
+        let shadow_ray = Ray { start: point + (l * 1e-4f32), dir: l };
+        if raytracer.find_any_collision(&shadow_ray, light_sample.distance) {
+            continue;
+        }
+
+        // 크기가 없는 조명은 BSDF 로 고른 방향이 닿을 수 없으므로 조명 샘플링만 사용
+        let weight = if light.is_area() {
+            power_heuristic(light.pdf(point, l, light_sample.distance), bsdf.pdf(v, l))
+        } else {
+            1.0
+        };
+
+        let radiance = light_sample.radiance.mul_element_wise(media.transmittance(light_sample.distance));
+        direct += f.mul_element_wise(radiance) * weight;
+    }
+
+    direct
+}
+
+// 두 방법으로 같은 빛을 샘플링할 때 a 쪽에 줄 무게
+// https://graphics.stanford.edu/courses/cs348b-03/papers/veach-chapter9.pdf
+pub fn power_heuristic(pdf_a: f32, pdf_b: f32) -> f32 {
+    let a = pdf_a * pdf_a;
+    let b = pdf_b * pdf_b;
+
+    if a + b > 0.0 {
+        a / (a + b)
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,283 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use cgmath::{ElementWise, InnerSpace};
+
+use crate::light::{pick_light, Light};
+use crate::medium::MediumStack;
+use crate::path::{next_visible_hit, sample_specular, select_lobe, Lobe};
+use crate::ray::Ray;
+use crate::raytracer::Raytracer;
+use crate::sampler::SampleStream;
+
+// 조명에서 나간 photon 이 거울, 유리를 지나 표면에 닿은 곳만 모아서 caustic 을 계산 (photon mapping)
+// 유리 구가 바닥에 모으는 빛처럼 카메라 쪽에서 시작하는 방법 (Whitted) 으로는 만들 수 없는 빛
+// Raytracer::photon_map 에 넣으면 Whitted 가 표면마다 주변의 photon 으로 밝기를 추정해서 더함
+// http://graphics.stanford.edu/courses/cs348b-00/course8.pdf (Jensen, A Practical Guide to Global Illumination using Photon Maps)
+#[derive(Debug, Clone, Copy)]
+pub struct PhotonMapping {
+    pub photons: u32, // 조명에서 내보내는 photon 개수 (모든 조명 합)
+    pub radius: f32, // 표면의 한 점에서 이 거리 안의 photon 으로 밝기를 추정
+}
+
+impl Default for PhotonMapping {
+    fn default() -> Self {
+        PhotonMapping { photons: 500_000, radius: 0.1 }
+    }
+}
+
+// 한 photon 이 거울, 유리에서 튕기는 최대 횟수
+const MAX_BOUNCES: u32 = 16;
+
+// 한 스레드가 한 번에 가져가서 내보내는 photon 개수
+const CHUNK_SIZE: u32 = 4096;
+
+// photon 이 샘플을 꺼내는 픽셀 자리, 화면에 없는 픽셀이라 카메라 샘플과 겹치지 않음
+// (k 번째 photon 이 픽셀 (0, 0) 의 k 번째 샘플과 같은 값을 쓰면 둘이 같이 치우침)
+pub const PHOTON_PIXEL: [u32; 2] = [u32::MAX, u32::MAX];
+
+impl PhotonMapping {
+    // 조명마다 빛을 내는 양 (면적 조명은 방향과 면적, 점 조명은 모든 방향) 에 맞춰 photon 을 나눠서 내보냄
+    pub fn build(&self, raytracer: &Raytracer) -> PhotonMap {
+        if raytracer.lights().is_empty() || self.photons == 0 {
+            return PhotonMap::new(Vec::new(), self.radius);
+        }
+
+        let chunks = self.photons.div_ceil(CHUNK_SIZE) as usize;
+        let threads = raytracer.thread_count().clamp(1, chunks);
+
+        // 각 스레드가 다음 묶음 번호를 가져가서 photon 을 내보냄 (tile 렌더링과 같은 방법)
+        // photon 은 번호로 샘플을 고르므로, 묶음 번호 순서로 합치면 스레드 수와 상관없이 같은 photon map
+        let next_chunk = AtomicUsize::new(0);
+
+        let mut traced: Vec<(usize, Vec<Photon>)> = thread::scope(|s| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| s.spawn(|| {
+                    let mut done = Vec::new();
+
+                    loop {
+                        let c = next_chunk.fetch_add(1, Ordering::Relaxed);
+                        if c >= chunks {
+                            break;
+                        }
+
+                        let start = c as u32 * CHUNK_SIZE;
+                        let end = (start + CHUNK_SIZE).min(self.photons);
+
+                        let mut photons = Vec::new();
+                        for k in start..end {
+                            self.emit(raytracer, k, &mut photons);
+                        }
+
+                        done.push((c, photons));
+                    }
+
+                    done
+                }))
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|w| w.join().expect("photon thread panicked"))
+                .collect()
+        });
+
+        traced.sort_by_key(|(c, _)| *c);
+
+        let photons = traced.into_iter().flat_map(|(_, photons)| photons).collect();
+        PhotonMap::new(photons, self.radius)
+    }
+
+    // k 번째 photon 을 내보내서 저장할 곳에 닿을 때마다 photons 에 더함
+    fn emit(&self, raytracer: &Raytracer, k: u32, photons: &mut Vec<Photon>) {
+        let lights = raytracer.lights();
+        let mut samples = SampleStream::new(raytracer.sampler.as_ref(), PHOTON_PIXEL, k);
+
+        let light = pick_light(lights, samples.next_1d());
+
+        // 방향이 없는 directional 조명은 내보내지 않음
+        let Some(emission) = light.sample_emission(samples.next_2d(), samples.next_2d()) else {
+            return;
+        };
+
+        let cos = emission.normal.map_or(1.0, |n| n.dot(emission.dir));
+        let scale = cos * lights.len() as f32 / (emission.pdf_pos * emission.pdf_dir * self.photons as f32);
+        let power = emission.radiance * scale;
+
+        let ray = Ray { start: emission.point + (emission.dir * 1e-4f32), dir: emission.dir };
+        trace_photon(raytracer, light, ray, power, &mut samples, photons);
+    }
+}
+
+// 거울, 유리에서는 path tracing 과 같은 비율로 반사/굴절을 골라서 이어가고, 한 번 이상 튕긴 뒤 표면 (diffuse) 에 닿으면 저장
+fn trace_photon<'a>(
+    raytracer: &'a Raytracer,
+    light: &Light,
+    mut ray: Ray,
+    mut power: cgmath::Vector3<f32>,
+    samples: &mut SampleStream,
+    photons: &mut Vec<Photon>,
+) {
+    let mut media = MediumStack::<'a>::new();
+    let mut bounces = 0;
+    let mut traveled = 0.0;
+
+    loop {
+        let next = next_visible_hit(raytracer, ray, media, false);
+        let hit = next.hit;
+        ray = next.ray;
+        media = next.media;
+
+        if hit.d < 0.0 {
+            break;
+        }
+
+        let Some(material) = hit.material else {
+            break;
+        };
+
+        traveled += next.distance;
+        power = power.mul_element_wise(next.transmittance);
+
+        let entering = ray.dir.dot(hit.normal) < 0.0;
+        let normal = if entering { hit.normal } else { -1.0 * hit.normal }.normalize();
+
+        // 조명에서 바로 온 빛은 Whitted 가 조명마다 계산하므로 저장하지 않음
+        // Whitted 가 표면 부분 (1 - reflection - transparency) 을 곱하므로 그대로 저장
+        let surface = 1.0 - material.reflection - material.transparency;
+        if bounces > 0 && surface > 0.0 {
+            // 점 조명의 attenuation 은 거울, 유리에서 튕긴 길이까지 합한 거리로 적용
+            // (Whitted 의 직접 조명은 조명에서 점까지의 거리, photon 은 조명에서 나온 뒤 지나온 전체 거리)
+            photons.push(Photon { pos: hit.point, dir: -ray.dir, power: power * light.falloff_scale(traveled) });
+        }
+
+        if bounces >= MAX_BOUNCES {
+            break;
+        }
+
+        // 표면 / 거울 반사 / 유리 중 하나를 비율대로 고름, 표면을 고르면 끝 (caustic 만 모음)
+        let lobe = select_lobe(material, samples.next_1d());
+        if lobe == Lobe::Surface {
+            break;
+        }
+
+        let Some((dir, weight, inner)) = sample_specular(material, &media, ray.dir, normal, entering, lobe == Lobe::Glass, samples) else {
+            break;
+        };
+
+        power *= weight;
+        media = inner;
+        bounces += 1;
+
+        ray = Ray { start: hit.point + (dir * 1e-4f32), dir };
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Photon {
+    pub pos: cgmath::Vector3<f32>,
+    pub dir: cgmath::Vector3<f32>, // photon 이 온 쪽을 향하는 방향 (조명 샘플의 l 과 같음)
+    pub power: cgmath::Vector3<f32>, // 이 photon 이 나르는 빛의 양 (flux)
+}
+
+// 위치로 찾을 수 있게 kd-tree 순서로 저장한 photon
+pub struct PhotonMap {
+    pub radius: f32,
+
+    // 구간 [lo, hi) 의 가운데 photon 이 노드, 앞쪽 절반과 뒤쪽 절반이 두 자식
+    photons: Vec<Photon>,
+    axes: Vec<u8>, // 노드마다 나눈 축 (x, y, z)
+}
+
+impl PhotonMap {
+    pub fn new(mut photons: Vec<Photon>, radius: f32) -> Self {
+        let mut axes = vec![0; photons.len()];
+        build_tree(&mut photons, &mut axes);
+
+        PhotonMap { radius, photons, axes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.photons.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.photons.is_empty()
+    }
+
+    // point 에서 radius 안에 있고 n 쪽에서 온 photon 마다 f 를 호출
+    pub fn gather(&self, point: cgmath::Vector3<f32>, n: cgmath::Vector3<f32>, mut f: impl FnMut(&Photon)) {
+        self.gather_range(0, self.photons.len(), point, self.radius * self.radius, &mut |photon| {
+            if photon.dir.dot(n) > 0.0 {
+                f(photon);
+            }
+        });
+    }
+
+    // point 에 radius 안의 photon 들이 비추는 빛의 양 (면적당 flux, 원판 안에 고르게 있다고 봄)
+    pub fn irradiance(&self, point: cgmath::Vector3<f32>, n: cgmath::Vector3<f32>) -> cgmath::Vector3<f32> {
+        let mut flux = cgmath::vec3(0.0, 0.0, 0.0);
+        self.gather(point, n, |photon| flux += photon.power);
+
+        flux / (std::f32::consts::PI * self.radius * self.radius)
+    }
+
+    fn gather_range(&self, lo: usize, hi: usize, point: cgmath::Vector3<f32>, r2: f32, f: &mut impl FnMut(&Photon)) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = (lo + hi) / 2;
+        let photon = &self.photons[mid];
+
+        if (photon.pos - point).magnitude2() <= r2 {
+            f(photon);
+        }
+
+        // point 가 있는 쪽을 먼저 보고, 나누는 면까지의 거리가 radius 보다 가까우면 반대쪽도 봄
+        let axis = self.axes[mid] as usize;
+        let delta = point[axis] - photon.pos[axis];
+        let (near, far) = if delta <= 0.0 { ((lo, mid), (mid + 1, hi)) } else { ((mid + 1, hi), (lo, mid)) };
+
+        self.gather_range(near.0, near.1, point, r2, f);
+
+        if delta * delta <= r2 {
+            self.gather_range(far.0, far.1, point, r2, f);
+        }
+    }
+}
+
+// 가장 넓게 퍼진 축의 가운데 값으로 나눔
+fn build_tree(photons: &mut [Photon], axes: &mut [u8]) {
+    if photons.len() <= 1 {
+        return;
+    }
+
+    let mut min = photons[0].pos;
+    let mut max = photons[0].pos;
+    for photon in photons.iter() {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(photon.pos[axis]);
+            max[axis] = max[axis].max(photon.pos[axis]);
+        }
+    }
+
+    let extent = max - min;
+    let axis = if extent.x >= extent.y && extent.x >= extent.z {
+        0
+    } else if extent.y >= extent.z {
+        1
+    } else {
+        2
+    };
+
+    let mid = photons.len() / 2;
+    photons.select_nth_unstable_by(mid, |a, b| a.pos[axis].total_cmp(&b.pos[axis]));
+    axes[mid] = axis as u8;
+
+    let (left, right) = photons.split_at_mut(mid);
+    let (left_axes, right_axes) = axes.split_at_mut(mid);
+
+    build_tree(left, left_axes);
+    build_tree(&mut right[1..], &mut right_axes[1..]);
+}
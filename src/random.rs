@@ -0,0 +1,47 @@
+// PCG32 난수 생성기
+// https://www.pcg-random.org/download.html (pcg32_random_r)
+// 같은 seed 면 항상 같은 수열을 만들기 때문에 스레드 수와 상관없이 같은 이미지가 나옴
+#[derive(Debug, Clone)]
+pub struct Pcg32 {
+    state: u64,
+    inc: u64,
+}
+
+impl Pcg32 {
+    // stream 이 다르면 seed 가 같아도 서로 다른 수열
+    pub fn new(seed: u64, stream: u64) -> Self {
+        let mut rng = Pcg32 { state: 0, inc: (stream << 1) | 1 };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(6364136223846793005).wrapping_add(self.inc);
+
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+
+    // [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        // 상위 24 bit 만 사용해야 1.0 이 나오지 않음
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    pub fn next_vec2(&mut self) -> cgmath::Vector2<f32> {
+        let x = self.next_f32();
+        let y = self.next_f32();
+        cgmath::vec2(x, y)
+    }
+}
+
+// splitmix64 의 마지막 단계, 비슷한 입력도 전혀 다른 값으로 섞음
+pub fn mix_u64(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
@@ -1,99 +1,132 @@
-use std::ops::{Add, Mul, Sub};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
 use std::time::Instant;
-use cgmath::{ElementWise, InnerSpace};
-use image::{EncodableLayout, ImageBuffer};
-use crate::hit::{Hit, Hittable, Object};
+use image::ImageBuffer;
+use crate::bsdf;
+use crate::bvh::Bvh;
+use crate::camera::Camera;
+use crate::film::{Film, SplatFilm};
+use crate::filter::Filter;
+use crate::hit::{Hit, Hittable};
 
 // https://doc.rust-kr.org/ch17-00-oop.html
 use crate::ray::Ray;
-use crate::sphere::Sphere;
 use crate::light::Light;
-use crate::square::Square;
-use crate::texture::Texture;
-use crate::triangle::Triangle;
-
-const RECURSIVE_LEVEL: i32 = 5;
+use crate::photon::PhotonMap;
+use crate::integrator::{Integrator, IntegratorKind, Splat};
+use crate::sampler::{SampleStream, Sampler, SamplerKind};
+use crate::sampling::{AdaptiveSampling, PixelSampling};
+use crate::scene::Scene;
+
+const TILE_SIZE: i32 = 16;
+
+// 화면의 [x0, x1) x [y0, y1) 영역
+struct Tile {
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+}
 
 pub struct Raytracer {
     pub width: i32,
     pub height: i32,
 
-    // sphere: Sphere,
-    light: Light,
+    camera: Camera,
+    lights: Vec<Light>,
 
     objects: Vec<Box<dyn Hittable>>,
+    bvh: Bvh,
+
+    // false 면 BVH 없이 모든 물체와 충돌 검사 (비교/디버깅용)
+    pub use_bvh: bool,
+
+    // 렌더링 스레드 수 (0 이면 CPU 코어 수만큼)
+    pub threads: usize,
+
+    // 픽셀마다 쏘는 광선 개수와 픽셀 안에서 위치를 고르는 방법
+    pub samples_per_pixel: u32,
+    pub pixel_sampling: PixelSampling,
+
+    // Some 이면 samples_per_pixel 대신 픽셀마다 오차에 따라 샘플 수 결정
+    pub adaptive: Option<AdaptiveSampling>,
+
+    // 픽셀 위치, 렌즈, 면적 조명 등 모든 [0, 1) 샘플을 만드는 곳
+    pub sampler: Box<dyn Sampler>,
+
+    // 샘플을 주변 픽셀에 나눠 주는 reconstruction filter
+    pub filter: Filter,
+
+    // 카메라 광선마다 색을 계산하는 방법 (Whitted, path tracing, ...)
+    pub integrator: Box<dyn Integrator>,
+
+    // 있으면 Whitted 가 거울, 유리를 지나 모인 빛 (caustic) 을 더함 (PhotonMapping::build 로 만듦)
+    pub photon_map: Option<PhotonMap>,
 }
 
-impl Raytracer {
-    pub fn new(width: i32, height: i32) -> Self {
-        // texture
-        let texture1 = Texture::new("./src/images/shadertoy_abstract1.jpg");
-        let texture2 = Texture::new("./src/images/back.jpg");
-
-        let mut sphere1 = Sphere::new(cgmath::vec3(0.0, -0.1, 1.5), 1.0);
-        sphere1.amb = cgmath::vec3(0.2, 0.2, 0.2);
-        sphere1.diff = cgmath::vec3(0.0, 0.0, 1.0);
-        sphere1.spec = cgmath::vec3(0.0, 0.0, 0.0);
-        sphere1.alpha = 50.0;
-        // sphere1.ks = 0.8;
-        sphere1.reflection = 0.0;
-        sphere1.transparency = 1.0;
-
-        let mut ground = Square::new(
-            // vertices
-            cgmath::vec3(-10.0, -1.5, 0.0),
-            cgmath::vec3(-10.0, -1.5, 10.0),
-            cgmath::vec3(10.0, -1.5, 10.0),
-            cgmath::vec3(10.0, -1.5, 0.0),
-            // uv
-            cgmath::vec2(0.0, 0.0),
-            cgmath::vec2(1.0, 0.0),
-            cgmath::vec2(1.0, 1.0),
-            cgmath::vec2(0.0, 1.0),
-        );
-        ground.amb = cgmath::vec3(1.0, 1.0, 1.0);
-        ground.diff = cgmath::vec3(1.0, 1.0, 1.0);
-        ground.spec = cgmath::vec3(1.0, 1.0, 1.0);
-        ground.alpha = 10.0;
-        ground.reflection = 0.0;
-
-        ground.amb_tex = Some(texture1.clone());
-        ground.dif_tex = Some(texture1.clone());
-
-        let mut square = Square::new(
-            // vertices
-            cgmath::vec3(-10.0, 10.0, 10.0),
-            cgmath::vec3(10.0, 10.0, 10.0),
-            cgmath::vec3(10.0, -10.0, 10.0),
-            cgmath::vec3(-10.0, -10.0, 10.0),
-            // uv
-            cgmath::vec2(0.0, 0.0),
-            cgmath::vec2(1.0, 0.0),
-            cgmath::vec2(1.0, 1.0),
-            cgmath::vec2(0.0, 1.0),
-        );
-        square.amb = cgmath::vec3(1.0, 1.0, 1.0);
-        square.diff = cgmath::vec3(0.0, 0.0, 0.0);
-        square.spec = cgmath::vec3(0.0, 0.0, 0.0);
-        square.alpha = 10.0;
-        square.reflection = 0.0;
+// 한 픽셀의 샘플 누적값
+#[derive(Default)]
+struct PixelStats {
+    luminance_sum: f32,
+    luminance_sq_sum: f32,
+    count: u32,
+}
 
-        square.amb_tex = Some(texture2.clone());
-        square.dif_tex = Some(texture2.clone());
+impl PixelStats {
+    fn add(&mut self, color: cgmath::Vector3<f32>) {
+        let luminance = bsdf::luminance(color);
 
-        let mut objects: Vec<Box<dyn Hittable>> = Vec::new();
+        self.luminance_sum += luminance;
+        self.luminance_sq_sum += luminance * luminance;
+        self.count += 1;
+    }
 
-        objects.push(Box::new(ground));
-        objects.push(Box::new(square));
-        objects.push(Box::new(sphere1));
+    // 평균 밝기의 표준 오차 sqrt(분산 / n)
+    fn error(&self) -> f32 {
+        if self.count < 2 {
+            return f32::MAX;
+        }
 
-        // located back of screen
-        let light = Light { pos: cgmath::vec3(0.0, 0.3, -0.5) };
+        let n = self.count as f32;
+        let mean = self.luminance_sum / n;
+        let variance = ((self.luminance_sq_sum - n * mean * mean) / (n - 1.0)).max(0.0);
 
-        Raytracer { width, height, light, objects }
+        (variance / n).sqrt()
     }
+}
+
+impl Raytracer {
+    pub fn new(width: i32, height: i32, scene: Scene) -> Self {
+        let bvh = Bvh::build(&scene.objects);
+
+        let mut camera = scene.camera;
+        camera.aspect = camera.aspect.or(Some(width as f32 / height as f32));
+
+        Raytracer {
+            width,
+            height,
+            camera,
+            lights: scene.lights,
+            objects: scene.objects,
+            bvh,
+            use_bvh: true,
+            threads: 0,
+            samples_per_pixel: 1,
+            pixel_sampling: PixelSampling::default(),
+            adaptive: None,
+            sampler: SamplerKind::default().create(0),
+            filter: Filter::default(),
+            integrator: IntegratorKind::default().create(),
+            photon_map: None,
+        }
+    }
+
+    pub fn find_closest_collision(&self, ray: &Ray) -> Hit<'_> {
+        if self.use_bvh {
+            return self.bvh.closest_hit(&self.objects, ray);
+        }
 
-    pub fn find_closest_collision(&self, ray: &Ray) -> Hit {
         let mut closest_hit = Hit::new(-1.0, cgmath::Vector3::new(0.0, 0.0, 0.0), cgmath::Vector3::new(0.0, 0.0, 0.0));
         let mut closest_distance = f32::MAX;
 
@@ -106,250 +139,267 @@ impl Raytracer {
 
             if hit.d < closest_distance {
                 closest_distance = hit.d;
-
-                closest_hit.d = hit.d;
-                closest_hit.normal = hit.normal;
-                closest_hit.point = hit.point;
-                closest_hit.uv = hit.uv;
-                closest_hit.object = Some(l.as_ref());
+                closest_hit = hit;
             }
         }
 
         closest_hit
     }
 
-    pub fn tracy_ray(&self, ray: &Ray, level: i32) -> cgmath::Vector3<f32> {
-        let mut color = cgmath::vec3(0.0, 0.0, 0.0);
-        let mut phong_color = cgmath::vec3(0.0, 0.0, 0.0);
-
-        if level < 0 {
-            return color;
-        }
+    // max_d 보다 가까운 물체가 하나라도 있으면 true (그림자 광선처럼 가장 가까운 충돌이 필요 없는 경우)
+    pub fn find_any_collision(&self, ray: &Ray, max_d: f32) -> bool {
+        self.any_collision(ray, max_d, |_| true)
+    }
 
-        let hit = self.find_closest_collision(ray);
+    // 광선을 따라 max_d 까지 빛이 통과하는 비율 (0 ~ 1)
+    // 투명한 물체는 재질의 transparency 만큼 빛을 통과시키고, 불투명한 물체를 만나면 바로 0
+    pub fn light_transmission(&self, ray: &Ray, max_d: f32) -> f32 {
+        let mut transmission = 1.0;
 
-        if hit.d >= 0.0 {
-            if let Some(object) = hit.object {
-                // 각 object 해당하는 프로퍼티 반환
-                let light_properties = object.get_light_color_properties();
-                let amb = light_properties.amb;
-                let diff = light_properties.diff;
-                let spec = light_properties.spec;
-                let alpha = light_properties.alpha;
+        self.any_collision(ray, max_d, |hit| {
+            transmission *= hit.material.map_or(0.0, |m| m.transparency);
+            transmission <= 0.0
+        });
 
-                // diffuse
-                let l = (self.light.pos - hit.point).normalize();
-                let n = hit.normal.normalize();
+        transmission
+    }
 
-                let diffuse = cgmath::dot(n, l).max(0.0) * diff;
+    fn any_collision<'a>(&'a self, ray: &Ray, max_d: f32, mut f: impl FnMut(&Hit<'a>) -> bool) -> bool {
+        if self.use_bvh {
+            return self.bvh.any_hit(&self.objects, ray, max_d, f);
+        }
 
-                // specular
-                let r = 2.0 * cgmath::dot(n, l) * n - l;
-                let e = (-1.0 * ray.dir).normalize();
+        self.objects.iter().any(|l| {
+            let hit = l.intersect_ray_collision(ray);
+            hit.d >= 0.0 && hit.d < max_d && f(&hit)
+        })
+    }
 
-                let specular = spec * cgmath::dot(r, e).max(0.0).powf(alpha);
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
 
-                let amb_texture = object.get_ambient_texture();
-                let dif_texture = object.get_diffuse_texture();
+    pub fn lights(&self) -> &[Light] {
+        &self.lights
+    }
 
-                let reflection = object.get_reflection();
-                let transparency = object.get_transparency();
+    // max_d 보다 가까이에서 광선이 닿는 면적 조명과 그 거리
+    pub fn find_light(&self, ray: &Ray, max_d: f32) -> Option<(f32, &Light)> {
+        let mut closest: Option<(f32, &Light)> = None;
 
-                // texture calculation - ambient
-                if amb_texture.is_some() {
-                    phong_color += amb.mul_element_wise(
-                        amb_texture.as_ref().expect("fail to access the texture").get_sample_linear(&hit.uv)
-                    );
-                } else {
-                    phong_color += amb;
+        for light in self.lights.iter() {
+            if let Some(d) = light.intersect(ray) {
+                if d < max_d && closest.is_none_or(|(closest_d, _)| d < closest_d) {
+                    closest = Some((d, light));
                 }
+            }
+        }
 
-                // texture calculation - diffuse
-                if dif_texture.is_some() {
-                    phong_color += diffuse.mul_element_wise(
-                        dif_texture.as_ref().expect("fail to access the texture").get_sample_linear(&hit.uv)
-                    );
-                } else {
-                    phong_color += diffuse;
-                }
+        closest
+    }
 
-                phong_color += specular;
+    // 픽셀 좌표 -> 화면 좌표 [0, 1] x [0, 1]
+    fn pixel_to_uv(&self, pos: cgmath::Vector2<f32>) -> cgmath::Vector2<f32> {
+        cgmath::vec2(pos.x / self.width as f32, pos.y / self.height as f32)
+    }
+
+    // 한 픽셀 안의 여러 위치로 광선을 쏴서 film 에 더하고, 사용한 샘플 수를 돌려줌 (super-sampling)
+    // tile 을 렌더링하는 각 스레드에서 호출
+    fn render_pixel(&self, i: i32, j: i32, film: &mut Film, splats: &mut Vec<Splat>) -> u32 {
+        let mut stats = PixelStats::default();
 
-                color += phong_color * (1.0 - reflection - transparency);
+        match self.adaptive {
+            None => {
+                self.trace_pixel_samples(i, j, self.samples_per_pixel.max(1), film, splats, &mut stats);
+            }
+            Some(adaptive) => {
+                // 처음 min_samples 개로 분산을 추정하고, 오차가 threshold 보다 크면 같은 개수씩 더 쏨
+                let batch = adaptive.min_samples.max(2).min(adaptive.max_samples.max(1));
 
-                if reflection > 0.0 {
-                    let m = -1.0 * hit.normal.dot(ray.dir) * hit.normal + ray.dir;
-                    let reflected_dir = ((2.0 * m) - ray.dir).normalize();
+                loop {
+                    self.trace_pixel_samples(i, j, batch, film, splats, &mut stats);
 
-                    let reflected_ray = Ray { start: hit.point + (reflected_dir * 1e-4f32), dir: reflected_dir };
-                    color += self.tracy_ray(&reflected_ray, level - 1);
+                    if stats.count + batch > adaptive.max_samples || stats.error() <= adaptive.threshold {
+                        break;
+                    }
                 }
+            }
+        }
 
-                // 참고
-                // https://samdriver.xyz/article/refraction-sphere (그림들이 좋아요)
-                // https://www.scratchapixel.com/lessons/3d-basic-rendering/introduction-to-shading/reflection-refraction-fresnel (오류있음)
-                // https://web.cse.ohio-state.edu/~shen.94/681/Site/Slides_files/reflection_refraction.pdf (슬라이드가 보기 좋지는 않지만 정확해요)
-                if transparency > 0.0 {
-                    const INDEX_OF_REFRACTION: f32 = 1.5;
+        stats.count
+    }
 
-                    let mut eta: f32;
-                    let mut normal: cgmath::Vector3<f32>;
+    // n 개의 샘플을 픽셀 전체에 고르게 뿌려서 film 과 stats 에 누적
+    // 샘플 번호는 픽셀 안에서 이어지고 (adaptive 에서 추가한 샘플도), 픽셀과 번호가 같으면 같은 샘플
+    fn trace_pixel_samples(&self, i: i32, j: i32, n_samples: u32, film: &mut Film, splats: &mut Vec<Splat>, stats: &mut PixelStats) {
+        for _ in 0..n_samples {
+            let index = stats.count;
+            let mut samples = SampleStream::new(self.sampler.as_ref(), [i as u32, j as u32], index);
 
-                    // 밖에서 안에서 들어가는 경우 (예: 공기->유리)
-                    if ray.dir.dot(hit.normal) < 0.0 {
-                        eta = INDEX_OF_REFRACTION;
-                        normal = hit.normal;
-                    }
-                    // 안에서 밖으로 나가는 경우 (예: 유리->공기)
-                    else {
-                        eta = 1.0 / INDEX_OF_REFRACTION;
-                        normal = -1.0 * hit.normal;
-                    }
+            let offset = self.pixel_sampling.offset(index, n_samples, samples.next_2d());
+            let pos = cgmath::vec2(i as f32 + offset.x, j as f32 + offset.y);
+            let uv = self.pixel_to_uv(pos);
 
-                    let cos_theta1 = -normal.dot(ray.dir);
-                    let sin_theta1 = (1.0 - cos_theta1 * cos_theta1).sqrt();
-                    let sin_theta2 = sin_theta1 / eta;
-                    let cos_theta2 = (1.0 - sin_theta2 * sin_theta2).sqrt();
+            // 샘플마다 렌즈 위의 다른 위치 (depth of field)
+            let lens = samples.next_2d();
+            let pixel_ray = self.camera.generate_ray(uv, lens);
 
-                    let m = (normal.dot(-ray.dir) * normal + ray.dir).normalize();
-                    let a = m * sin_theta2;
-                    let b = -normal * cos_theta2;
-                    let refracted_dir = (a + b).normalize();
+            let color = self.integrator.radiance_with_splats(self, &pixel_ray, &mut samples, splats);
 
-                    let refracted_ray = Ray { start: hit.point + (refracted_dir * 1e-4f32), dir: refracted_dir };
-                    color += self.tracy_ray(&refracted_ray, level - 1) * transparency;
-                }
+            film.add_sample(pos, color, &self.filter);
+            stats.add(color);
+        }
+    }
+
+    // tile 의 film (필터가 닿는 만큼 tile 보다 넓음) 과 픽셀마다 사용한 샘플 수, 다른 픽셀에 더할 빛
+    fn render_tile(&self, tile: &Tile) -> (Film, Vec<u32>, Vec<Splat>) {
+        let border = (self.filter.radius + 0.5).ceil() as i32;
+        let mut film = Film::new(
+            tile.x0 - border,
+            tile.y0 - border,
+            tile.x1 - tile.x0 + border * 2,
+            tile.y1 - tile.y0 + border * 2,
+        );
+
+        let mut sample_counts = Vec::with_capacity(((tile.x1 - tile.x0) * (tile.y1 - tile.y0)) as usize);
+        let mut splats = Vec::new();
+
+        for j in tile.y0..tile.y1 {
+            for i in tile.x0..tile.x1 {
+                sample_counts.push(self.render_pixel(i, j, &mut film, &mut splats));
             }
         }
 
-        color
+        (film, sample_counts, splats)
     }
 
-    fn trace_ray_2x2(&self, eye_pos: &cgmath::Vector3<f32>, pixel_pos: &cgmath::Vector3<f32>, dx: f32, level: i32) -> cgmath::Vector3<f32> {
-        if level == 0 {
-            let ray = Ray { dir: (pixel_pos - eye_pos).normalize(), start: *pixel_pos };
-            self.tracy_ray(&ray, 0)
-        } else {
-            let sub_dx = 0.5 * dx;
-
-            let mut pixel_color: cgmath::Vector3<f32> = cgmath::vec3(0.0, 0.0, 0.0);
-            let mut pos = cgmath::vec3(
-                pixel_pos.x - sub_dx * 0.5,
-                pixel_pos.y - sub_dx * 0.5,
-                pixel_pos.z,
-            );
-
-            for j in 0..2 {
-                for i in 0..2 {
-                    let sub_pos = cgmath::vec3(
-                        pos.x + (i as f32) * sub_dx,
-                        pos.y + (j as f32) * sub_dx,
-                        pos.z,
-                    );
-                    let color = self.trace_ray_2x2(eye_pos, &sub_pos, sub_dx, level - 1);
-                    pixel_color += color;
-                }
+    fn tiles(&self) -> Vec<Tile> {
+        let mut tiles = Vec::new();
+
+        for y0 in (0..self.height).step_by(TILE_SIZE as usize) {
+            for x0 in (0..self.width).step_by(TILE_SIZE as usize) {
+                tiles.push(Tile {
+                    x0,
+                    y0,
+                    x1: (x0 + TILE_SIZE).min(self.width),
+                    y1: (y0 + TILE_SIZE).min(self.height),
+                });
             }
+        }
 
-            pixel_color.mul(0.25)
+        tiles
+    }
+
+    // 렌더링 (와 photon map) 에 쓰는 스레드 수, threads 가 0 이면 CPU 코어 수
+    pub fn thread_count(&self) -> usize {
+        if self.threads > 0 {
+            self.threads
+        } else {
+            thread::available_parallelism().map_or(1, |n| n.get())
         }
     }
 
     pub fn render(&self, imgbuf: &mut ImageBuffer<image::Rgb<u8>, Vec<u8>>) {
+        self.render_with_sample_map(imgbuf, None);
+    }
+
+    // sample_map: 픽셀마다 사용한 샘플 수 (디버깅용, 최대 샘플 수가 255)
+    pub fn render_with_sample_map(
+        &self,
+        imgbuf: &mut ImageBuffer<image::Rgb<u8>, Vec<u8>>,
+        sample_map: Option<&mut image::GrayImage>,
+    ) {
         println!("start of render!");
 
         let start = Instant::now();
 
-        let eye_pos = cgmath::Vector3::new(0.0, 0.0, -1.5);
+        let tiles = self.tiles();
+        let threads = self.thread_count().clamp(1, tiles.len().max(1));
 
-        let dx = 2.0 / self.height as f32;
+        // 각 스레드가 다음 tile 번호를 가져가서 렌더링
+        // 픽셀 색은 어느 스레드에서 계산해도 같으므로 결과는 스레드 수와 무관
+        let next_tile = AtomicUsize::new(0);
+        let splat_film = Mutex::new(SplatFilm::new(self.width, self.height));
 
-        for j in 0..self.height {
-            for i in 0..self.width {
-                let pixel_pos_world = self.transform_screen_to_world(cgmath::vec2(i as f32, j as f32));
+        let mut rendered: Vec<(usize, (Film, Vec<u32>))> = thread::scope(|s| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| s.spawn(|| {
+                    let mut done = Vec::new();
 
-                // 광선의 방향 벡터
-                // 스크린에 수직인 z 방향, 유닛벡터
-                // let ray_dir = cgmath::vec3(0.0, 0.0, 1.0);
-                let ray_dir = (pixel_pos_world - eye_pos).normalize();
+                    loop {
+                        let t = next_tile.fetch_add(1, Ordering::Relaxed);
+                        if t >= tiles.len() {
+                            break;
+                        }
 
-                // cgmath::vec3(0.0, 0.0, 1.0);
+                        let (film, sample_counts, splats) = self.render_tile(&tiles[t]);
+                        splat_film.lock().unwrap().add_tile(t, splats);
 
-                // general
-                let pixel_ray = Ray { dir: ray_dir, start: pixel_pos_world };
-                let color = self.tracy_ray(&pixel_ray, RECURSIVE_LEVEL);
+                        done.push((t, (film, sample_counts)));
+                    }
 
-                // super-sampling
-                // let color = self.trace_ray_2x2(&eye_pos, &pixel_pos_world, dx, 3);
+                    done
+                }))
+                .collect();
 
-                let r = (color.x * 255.0).clamp(0.0, 255.0) as u8;
-                let g = (color.y * 255.0).clamp(0.0, 255.0) as u8;
-                let b = (color.z * 255.0).clamp(0.0, 255.0) as u8;
+            workers
+                .into_iter()
+                .flat_map(|w| w.join().expect("render thread panicked"))
+                .collect()
+        });
 
-                imgbuf.put_pixel(i as u32, j as u32, image::Rgb([r, g, b]));
-            }
-        }
+        // tile 경계의 픽셀은 여러 tile 의 샘플을 받으므로, 항상 tile 번호 순서로 더해서 같은 결과를 만듦
+        rendered.sort_by_key(|(t, _)| *t);
 
-        let elapsed = start.elapsed();
+        let mut film = Film::new(0, 0, self.width, self.height);
+        for (_, (tile_film, _)) in rendered.iter() {
+            film.merge(tile_film);
+        }
 
-        println!("end of render! {:?} ms", elapsed.as_millis());
+        // splat 은 모든 카메라 샘플에서 나오므로 픽셀당 평균 샘플 수로 나눔
+        let splat_film = splat_film.into_inner().unwrap();
+        let total_samples: u32 = rendered.iter().flat_map(|(_, (_, sample_counts))| sample_counts.iter()).sum();
+        let splat_scale = if splat_film.is_empty() { None } else { Some((self.width * self.height) as f32 / total_samples as f32) };
 
-        // For debugging
-        image::save_buffer("tmp_ray_result.png", imgbuf.as_bytes(), self.width as u32, self.height as u32, image::ExtendedColorType::Rgb8).unwrap();
-    }
+        for j in 0..self.height {
+            for i in 0..self.width {
+                let mut color = film.color(i, j);
 
-    fn transform_screen_to_world(&self, pos: cgmath::Vector2<f32>) -> cgmath::Vector3<f32> {
-        let w = self.width as f32;
-        let h = self.height as f32;
-        let x_scale = 2.0 / w;
-        let y_scale = 2.0 / h;
-        let aspect = w / h;
-
-        cgmath::vec3(
-            (pos.x * x_scale - 1.0) * aspect,
-            -pos.y * y_scale + 1.0,
-            0.0,
-        )
-    }
+                if let Some(scale) = splat_scale {
+                    color += splat_film.color(i, j) * scale;
+                }
 
-    fn calculate_phong_model_color(
-        &self,
-        hit: &Hit,
-        ray: &Ray,
-        object: &Object,
-    ) -> cgmath::Vector3<f32> {
-        let mut color = cgmath::vec3(0.0, 0.0, 0.0);
+                let r = (color.x * 255.0).clamp(0.0, 255.0) as u8;
+                let g = (color.y * 255.0).clamp(0.0, 255.0) as u8;
+                let b = (color.z * 255.0).clamp(0.0, 255.0) as u8;
 
-        // diffuse
-        let l = (self.light.pos - hit.point).normalize();
-        let n = hit.normal.normalize();
+                imgbuf.put_pixel(i as u32, j as u32, image::Rgb([r, g, b]));
+            }
+        }
 
-        let diff = cgmath::dot(n, l).max(0.0);
+        let max_samples = match self.adaptive {
+            Some(adaptive) => adaptive.max_samples,
+            None => self.samples_per_pixel,
+        }.max(1);
 
-        // specular
-        let r = 2.0 * cgmath::dot(n, l) * n - l;
-        let e = (-1.0 * ray.dir).normalize();
+        if let Some(sample_map) = sample_map {
+            for (t, (_, sample_counts)) in rendered.iter() {
+                let tile = &tiles[*t];
+                let tile_width = (tile.x1 - tile.x0) as usize;
 
-        let specular = cgmath::dot(r, e)
-            .max(0.0);
+                for (k, samples) in sample_counts.iter().enumerate() {
+                    let i = tile.x0 + (k % tile_width) as i32;
+                    let j = tile.y0 + (k / tile_width) as i32;
 
-        match object {
-            Object::Sphere(s) => {
-                let specular_pow = specular.powf(s.alpha);
-                color = s.amb + (s.diff * diff) + (s.spec * specular_pow);
-            }
-            Object::Triangle(t) => {
-                let specular_pow = specular.powf(t.alpha);
-                color = t.amb + (t.diff * diff) + (t.spec * specular_pow);
-            }
-            Object::Square(s) => {
-                let specular_pow = specular.powf(s.alpha);
-                color = s.amb + (s.diff * diff) + (s.spec * specular_pow);
+                    let v = (*samples as f32 / max_samples as f32 * 255.0).clamp(0.0, 255.0) as u8;
+                    sample_map.put_pixel(i as u32, j as u32, image::Luma([v]));
+                }
             }
-            _ => {}
         }
 
-        color
+        let elapsed = start.elapsed();
+
+        println!("end of render! {:?} ms ({} threads)", elapsed.as_millis(), threads);
     }
 
     // fn get_ambient_color(&self, object: &dyn Hittable) -> cgmath::Vector3<f32> {
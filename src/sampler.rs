@@ -0,0 +1,273 @@
+use std::str::FromStr;
+
+use crate::random::{mix_u64, Pcg32};
+
+// 픽셀 (x, y) 의 index 번째 샘플에서 dimension 번째 [0, 1) 값
+// 같은 인자면 항상 같은 값을 돌려줘야 함 (스레드 수, 렌더링 순서와 상관없이 같은 이미지)
+//
+// 차원은 쓰는 순서대로 번호를 붙임: 0, 1 픽셀 안 위치, 2, 3 렌즈, 그 다음부터 조명/반사 샘플
+// 보통 SampleStream 으로 차례대로 꺼내 씀
+pub trait Sampler: Send + Sync {
+    fn sample(&self, pixel: [u32; 2], index: u32, dimension: u32) -> f32;
+}
+
+// 한 픽셀의 한 샘플에서 다음 차원을 차례대로 꺼내는 상태
+pub struct SampleStream<'a> {
+    sampler: &'a dyn Sampler,
+    pixel: [u32; 2],
+    index: u32,
+    dimension: u32,
+}
+
+impl<'a> SampleStream<'a> {
+    pub fn new(sampler: &'a dyn Sampler, pixel: [u32; 2], index: u32) -> Self {
+        SampleStream { sampler, pixel, index, dimension: 0 }
+    }
+
+    pub fn next_1d(&mut self) -> f32 {
+        let u = self.sampler.sample(self.pixel, self.index, self.dimension);
+        self.dimension += 1;
+        u
+    }
+
+    pub fn next_2d(&mut self) -> cgmath::Vector2<f32> {
+        let x = self.next_1d();
+        let y = self.next_1d();
+        cgmath::vec2(x, y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SamplerKind {
+    #[default]
+    Random,
+    Halton,
+    Sobol,
+    BlueNoise,
+}
+
+impl SamplerKind {
+    pub fn create(&self, seed: u64) -> Box<dyn Sampler> {
+        match self {
+            SamplerKind::Random => Box::new(RandomSampler { seed }),
+            SamplerKind::Halton => Box::new(HaltonSampler { seed }),
+            SamplerKind::Sobol => Box::new(SobolSampler { seed }),
+            SamplerKind::BlueNoise => Box::new(BlueNoiseSampler::new(seed)),
+        }
+    }
+}
+
+impl FromStr for SamplerKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "random" => Ok(SamplerKind::Random),
+            "halton" => Ok(SamplerKind::Halton),
+            "sobol" => Ok(SamplerKind::Sobol),
+            "blue-noise" => Ok(SamplerKind::BlueNoise),
+            _ => Err(anyhow::anyhow!("unknown sampler '{}', expected random, halton, sobol or blue-noise", s)),
+        }
+    }
+}
+
+fn hash_pixel(pixel: [u32; 2], seed: u64) -> u64 {
+    mix_u64(seed ^ mix_u64(((pixel[1] as u64) << 32) | pixel[0] as u64))
+}
+
+fn to_f32(x: u32) -> f32 {
+    // 상위 24 bit 만 사용해야 1.0 이 나오지 않음
+    (x >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+}
+
+// 서로 독립인 PCG 난수
+pub struct RandomSampler {
+    pub seed: u64,
+}
+
+impl Sampler for RandomSampler {
+    fn sample(&self, pixel: [u32; 2], index: u32, dimension: u32) -> f32 {
+        let seed = mix_u64(hash_pixel(pixel, self.seed) ^ index as u64);
+        Pcg32::new(seed, dimension as u64).next_f32()
+    }
+}
+
+const PRIMES: [u32; 64] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101, 103,
+    107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193, 197, 199, 211, 223,
+    227, 229, 233, 239, 241, 251, 257, 263, 269, 271, 277, 281, 283, 293, 307, 311,
+];
+
+// 차원마다 다른 소수 진법의 radical inverse
+// 픽셀마다 차원별로 값을 무작위로 평행 이동 (Cranley-Patterson rotation)
+// 소수 표보다 많은 차원은 난수로 채움
+pub struct HaltonSampler {
+    pub seed: u64,
+}
+
+fn radical_inverse(base: u32, mut index: u32) -> f32 {
+    let inv_base = 1.0 / base as f64;
+    let mut inv = inv_base;
+    let mut result = 0.0f64;
+
+    while index > 0 {
+        result += (index % base) as f64 * inv;
+        index /= base;
+        inv *= inv_base;
+    }
+
+    result as f32
+}
+
+impl Sampler for HaltonSampler {
+    fn sample(&self, pixel: [u32; 2], index: u32, dimension: u32) -> f32 {
+        let Some(&base) = PRIMES.get(dimension as usize) else {
+            return RandomSampler { seed: self.seed }.sample(pixel, index, dimension);
+        };
+
+        let shift = to_f32(mix_u64(hash_pixel(pixel, self.seed) ^ dimension as u64) as u32);
+        let u = radical_inverse(base, index) + shift;
+
+        // 반올림으로 1.0 이 되는 경우 방지
+        (u - u.floor()).min(1.0 - f32::EPSILON)
+    }
+}
+
+// Owen scrambling 한 Sobol 수열
+// https://jcgt.org/published/0009/04/01/ (Burley, Practical Hash-based Owen Scrambling)
+//
+// 2 차원 Sobol 을 차원 쌍마다 다른 seed 로 scramble 해서 붙여 씀 (padding)
+// 차원 수에 제한이 없고, 픽셀마다 샘플 순서도 섞어서 픽셀 간 상관관계가 없음
+pub struct SobolSampler {
+    pub seed: u64,
+}
+
+fn sobol_2d(index: u32) -> [u32; 2] {
+    let mut x = 0u32;
+    let mut y = 0u32;
+    let mut v = 1u32 << 31;
+
+    // 첫 번째 차원은 van der Corput, 두 번째는 원시 다항식 x + 1 의 방향 수
+    for bit in 0..32 {
+        if (index >> bit) & 1 != 0 {
+            x ^= 1u32 << (31 - bit);
+            y ^= v;
+        }
+        v ^= v >> 1;
+    }
+
+    [x, y]
+}
+
+fn laine_karras_permutation(mut x: u32, seed: u32) -> u32 {
+    x = x.wrapping_add(seed);
+    x ^= x.wrapping_mul(0x6c50b47c);
+    x ^= x.wrapping_mul(0xb82f1e52);
+    x ^= x.wrapping_mul(0xc7afe638);
+    x ^= x.wrapping_mul(0x8d22f6e6);
+    x
+}
+
+fn nested_uniform_scramble(x: u32, seed: u32) -> u32 {
+    laine_karras_permutation(x.reverse_bits(), seed).reverse_bits()
+}
+
+fn owen_sobol(index: u32, dimension: u32, seed: u64) -> u32 {
+    // 차원 쌍마다 다른 seed
+    let seed = mix_u64(seed ^ (dimension / 2) as u64);
+
+    let index = nested_uniform_scramble(index, seed as u32);
+    let x = sobol_2d(index)[(dimension % 2) as usize];
+
+    nested_uniform_scramble(x, (seed >> 32) as u32 ^ (dimension % 2))
+}
+
+impl Sampler for SobolSampler {
+    fn sample(&self, pixel: [u32; 2], index: u32, dimension: u32) -> f32 {
+        to_f32(owen_sobol(index, dimension, hash_pixel(pixel, self.seed)))
+    }
+}
+
+const BLUE_NOISE_SIZE: usize = 64;
+
+// 모든 픽셀이 같은 Owen-scrambled Sobol 을 쓰고, 차원마다 blue noise 텍스처 값만큼 평행 이동
+// 샘플 수가 적을 때 오차가 이웃 픽셀끼리 고르게 흩어져서 (고주파 노이즈) 눈에 덜 띔
+// 텍스처는 화면에 반복해서 깔고, 차원마다 다른 위치에서 읽음
+pub struct BlueNoiseSampler {
+    pub seed: u64,
+    tile: Vec<f32>, // BLUE_NOISE_SIZE x BLUE_NOISE_SIZE, [0, 1) 의 서로 다른 값
+}
+
+impl BlueNoiseSampler {
+    pub fn new(seed: u64) -> Self {
+        BlueNoiseSampler { seed, tile: blue_noise_tile(BLUE_NOISE_SIZE, seed) }
+    }
+}
+
+impl Sampler for BlueNoiseSampler {
+    fn sample(&self, pixel: [u32; 2], index: u32, dimension: u32) -> f32 {
+        let offset = mix_u64(self.seed ^ mix_u64(dimension as u64 + 1));
+        let x = (pixel[0] as usize + (offset as usize)) % BLUE_NOISE_SIZE;
+        let y = (pixel[1] as usize + (offset >> 32) as usize) % BLUE_NOISE_SIZE;
+
+        let u = to_f32(owen_sobol(index, dimension, self.seed)) + self.tile[y * BLUE_NOISE_SIZE + x];
+        (u - u.floor()).min(1.0 - f32::EPSILON)
+    }
+}
+
+// size x size blue noise (반복해서 깔아도 이음새가 없음)
+// void-and-cluster 에서 가장 큰 빈 곳을 차례대로 채우는 단계만 사용
+// https://blog.demofox.org/2019/06/25/generating-blue-noise-textures-with-void-and-cluster/
+// 각 칸의 값은 채운 순서 / (size * size)
+fn blue_noise_tile(size: usize, seed: u64) -> Vec<f32> {
+    const SIGMA: f32 = 1.5;
+
+    let n = size * size;
+    let mut rng = Pcg32::new(seed, 0x5eed);
+
+    // 가장자리를 넘어가도 이어지는 거리의 gaussian
+    let radius = (SIGMA * 4.0).ceil() as i32;
+    let mut kernel = Vec::new();
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            let w = (-((dx * dx + dy * dy) as f32) / (2.0 * SIGMA * SIGMA)).exp();
+            kernel.push((dx, dy, w));
+        }
+    }
+
+    let mut energy = vec![0.0f32; n];
+    let mut filled = vec![false; n];
+    let mut rank = vec![0usize; n];
+
+    let splat = |energy: &mut [f32], p: usize| {
+        let (px, py) = ((p % size) as i32, (p / size) as i32);
+
+        for &(dx, dy, w) in kernel.iter() {
+            let x = (px + dx).rem_euclid(size as i32) as usize;
+            let y = (py + dy).rem_euclid(size as i32) as usize;
+            energy[y * size + x] += w;
+        }
+    };
+
+    // 가장 비어있는 곳 (에너지가 가장 낮은 빈 칸) 을 차례대로 채움
+    // 같은 에너지면 작은 난수 값으로 순서를 정해서 격자 모양이 생기지 않게 함
+    let jitter: Vec<f32> = (0..n).map(|_| rng.next_f32() * 1e-3).collect();
+
+    for r in 0..n {
+        let mut best = usize::MAX;
+        let mut best_energy = f32::MAX;
+
+        for p in 0..n {
+            if !filled[p] && energy[p] + jitter[p] < best_energy {
+                best = p;
+                best_energy = energy[p] + jitter[p];
+            }
+        }
+
+        filled[best] = true;
+        rank[best] = r;
+        splat(&mut energy, best);
+    }
+
+    rank.iter().map(|&r| (r as f32 + 0.5) / n as f32).collect()
+}
@@ -0,0 +1,138 @@
+use std::f32::consts::PI;
+use std::str::FromStr;
+
+use cgmath::InnerSpace;
+
+// 한 픽셀 안에서 샘플 위치를 고르는 방법
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PixelSampling {
+    // 격자 칸의 중심 (샘플 1개면 픽셀 중심)
+    #[default]
+    Regular,
+    // 격자 칸 안에서 무작위 위치 (jittered)
+    Stratified,
+    // 픽셀 전체에서 sampler 가 고른 위치 (low-discrepancy sampler 와 같이 쓰면 가장 고르게 퍼짐)
+    Random,
+}
+
+impl PixelSampling {
+    // n 개 중 k 번째 샘플의 픽셀 안 위치 [0, 1)^2
+    // u: sampler 에서 꺼낸 [0, 1)^2 값 (regular 는 무시)
+    // 격자를 채우고 남는 샘플은 stratify 가 나누지 않으므로 regular 는 픽셀 중심, stratified 는 픽셀 전체에서 고름
+    pub fn offset(&self, k: u32, n: u32, u: cgmath::Vector2<f32>) -> cgmath::Vector2<f32> {
+        match self {
+            PixelSampling::Regular => stratify(k, n, cgmath::vec2(0.5, 0.5)),
+            PixelSampling::Stratified => stratify(k, n, u),
+            PixelSampling::Random => u,
+        }
+    }
+}
+
+impl FromStr for PixelSampling {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "regular" => Ok(PixelSampling::Regular),
+            "stratified" => Ok(PixelSampling::Stratified),
+            "random" => Ok(PixelSampling::Random),
+            _ => Err(anyhow::anyhow!("unknown pixel sampling '{}', expected regular, stratified or random", s)),
+        }
+    }
+}
+
+// 평탄한 곳은 적게, 경계처럼 색이 많이 변하는 곳만 더 많이 샘플링
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveSampling {
+    pub min_samples: u32, // 처음 쏘는 샘플 수 (이후 이만큼씩 추가)
+    pub max_samples: u32,
+    pub threshold: f32, // 평균 밝기의 표준 오차가 이보다 작으면 중단
+}
+
+// n 과 수직인 두 단위 벡터 (t, b)
+// https://graphics.pixar.com/library/OrthonormalB/paper.pdf
+pub fn orthonormal_basis(n: cgmath::Vector3<f32>) -> (cgmath::Vector3<f32>, cgmath::Vector3<f32>) {
+    let sign = 1.0f32.copysign(n.z);
+    let a = -1.0 / (sign + n.z);
+    let b = n.x * n.y * a;
+
+    let t = cgmath::vec3(1.0 + sign * n.x * n.x * a, sign * b, -sign * n.x);
+    let bt = cgmath::vec3(b, sign + n.y * n.y * a, -n.y);
+
+    (t, bt)
+}
+
+// n 개의 샘플 중 k 번째가 속하는 격자 칸 안의 위치 (stratified sampling)
+// u 는 칸 안에서의 [0, 1)^2 위치
+// 격자 (nx * ny <= n) 를 채우고 남는 샘플은 나누지 않고 전체에서 u 로 고름 (비어 있는 칸이 생기지 않음)
+pub fn stratify(k: u32, n: u32, u: cgmath::Vector2<f32>) -> cgmath::Vector2<f32> {
+    let n = n.max(1);
+    let nx = ((n as f32).sqrt() as u32).max(1);
+    let ny = n / nx;
+
+    let k = k % n;
+    if k >= nx * ny {
+        return u;
+    }
+
+    let x = k % nx;
+    let y = k / nx;
+
+    cgmath::vec2((x as f32 + u.x) / nx as f32, (y as f32 + u.y) / ny as f32)
+}
+
+// 단위 원판 위의 균일한 점 (concentric mapping)
+// https://pbr-book.org/3ed-2018/Monte_Carlo_Integration/2D_Sampling_with_Multidimensional_Transformations
+pub fn sample_disk(u: cgmath::Vector2<f32>) -> cgmath::Vector2<f32> {
+    let o = u * 2.0 - cgmath::vec2(1.0, 1.0);
+
+    if o.x == 0.0 && o.y == 0.0 {
+        return cgmath::vec2(0.0, 0.0);
+    }
+
+    let (r, theta) = if o.x.abs() > o.y.abs() {
+        (o.x, PI / 4.0 * (o.y / o.x))
+    } else {
+        (o.y, PI / 2.0 - PI / 4.0 * (o.x / o.y))
+    };
+
+    cgmath::vec2(theta.cos(), theta.sin()) * r
+}
+
+// axis 를 중심으로 cos_max 안쪽 원뿔 안의 균일한 방향
+pub fn sample_cone(axis: cgmath::Vector3<f32>, cos_max: f32, u: cgmath::Vector2<f32>) -> cgmath::Vector3<f32> {
+    let cos_theta = 1.0 - u.x * (1.0 - cos_max);
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let phi = 2.0 * PI * u.y;
+
+    let (t, b) = orthonormal_basis(axis);
+
+    (t * (sin_theta * phi.cos()) + b * (sin_theta * phi.sin()) + axis * cos_theta).normalize()
+}
+
+// n 쪽 반구에서 cos 에 비례하는 방향 (원판 위의 균일한 점을 반구로 올림, Malley's method)
+// 확률 밀도는 cos / PI
+pub fn sample_cosine_hemisphere(n: cgmath::Vector3<f32>, u: cgmath::Vector2<f32>) -> cgmath::Vector3<f32> {
+    let d = sample_disk(u);
+    let z = (1.0 - d.x * d.x - d.y * d.y).max(0.0).sqrt();
+
+    let (t, b) = orthonormal_basis(n);
+
+    (t * d.x + b * d.y + n * z).normalize()
+}
+
+// 외접원 반지름이 1인 정 n 각형 안의 균일한 점 (조리개 날개 모양 bokeh)
+pub fn sample_polygon(n: u32, u: cgmath::Vector2<f32>) -> cgmath::Vector2<f32> {
+    // 중심과 두 꼭짓점으로 이루어진 n 개의 삼각형 중 하나를 고름
+    let x = u.x * n as f32;
+    let sector = (x as u32).min(n - 1);
+    let ux = x - sector as f32;
+
+    let angle = 2.0 * PI / n as f32;
+    let a = cgmath::vec2((sector as f32 * angle).cos(), (sector as f32 * angle).sin());
+    let b = cgmath::vec2(((sector + 1) as f32 * angle).cos(), ((sector + 1) as f32 * angle).sin());
+
+    // 삼각형 (0, a, b) 위의 균일한 점
+    let su = ux.sqrt();
+    a * (su * (1.0 - u.y)) + b * (su * u.y)
+}
@@ -0,0 +1,695 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context};
+use cgmath::InnerSpace;
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::Deserialize;
+
+use crate::camera::{Camera, Projection};
+use crate::hit::Hittable;
+use crate::light::{Light, LightKind};
+use crate::material::{Material, Shading};
+use crate::microfacet::MetallicRoughness;
+use crate::optics::Fresnel;
+use crate::sphere::Sphere;
+use crate::square::Square;
+use crate::texture::Texture;
+use crate::triangle::Triangle;
+
+/*
+ * 장면(scene) 파일 형식 (TOML)
+ *
+ * [camera]
+ * pos = [0.0, 0.0, -1.5]
+ * look_at = [0.0, 0.0, 0.0]
+ * up = [0.0, 1.0, 0.0]
+ * projection = "perspective" # perspective | orthographic | panoramic
+ * fov = 67.38                # perspective: vertical field of view (degree)
+ * ortho_height = 2.0         # orthographic: 화면 세로 크기 (world 단위)
+ * aspect = 1.333             # 생략하면 이미지 비율
+ * aperture = 0.05            # 렌즈 반지름 (0 이면 pinhole, depth of field 없음)
+ * focus_distance = 3.0       # 초점 평면까지 거리, 생략하면 look_at 까지 거리
+ * blades = 6                 # 조리개 날개 수 (0 이면 원형 bokeh)
+ *
+ * [[lights]]
+ * type = "point"            # point | directional | spot | rect | disk | sphere
+ * pos = [0.0, 0.3, -0.5]     # point, spot, disk/sphere 의 중심
+ * dir = [0.0, -1.0, 0.0]     # directional, spot (빛이 진행하는 방향), disk (빛이 나가는 면의 방향)
+ * cone_angle = 30.0          # spot: 원뿔 반각 (degree)
+ * soft_edge = 5.0            # spot: 가장자리가 부드러워지는 각도 (degree)
+ * corner = [-0.5, 2.0, 1.0]  # rect: 한 꼭짓점과 두 변, 빛은 edge_u x edge_v 방향으로 나감
+ * edge_u = [1.0, 0.0, 0.0]
+ * edge_v = [0.0, 0.0, 1.0]
+ * radius = 0.5               # disk, sphere
+ * samples = 16               # 면적 조명의 그림자 광선 개수
+ * color = [1.0, 1.0, 1.0]
+ * intensity = 3.14159         # 흰 diffuse 면이 정면에서 받으면 밝기 1 인 세기 (기본값 PI)
+ * attenuation = [0.0, 0.0, 1.0]  # 1 / (c + l * d + q * d^2), 기본값은 거리 제곱 감쇠
+ *
+ * [[objects]]
+ * type = "sphere"            # sphere | triangle | square
+ * center = [0.0, -0.1, 1.5]
+ * radius = 1.0
+ *
+ * [objects.material]
+ * model = "phong"            # phong | metallic-roughness
+ * amb = [0.2, 0.2, 0.2]      # Phong: amb, diff, spec, ks, alpha
+ * base_color = [0.9, 0.6, 0.2]  # metallic-roughness: base_color, metallic, specular (GGX)
+ * metallic = 1.0
+ * transparency = 1.0
+ * roughness = 0.3            # 반사/굴절이 흐려지는 정도 (metallic-roughness 는 하이라이트에도 사용, 기본값 0.5)
+ * amb_tex = "back.jpg"       # amb_tex, dif_tex: amb, diff 에 곱하는 텍스처
+ * ior = 1.5                  # 굴절률 (transparency 가 있을 때)
+ * fresnel = "exact"          # exact | schlick, 경계면에서 반사/굴절 비율 계산
+ * priority = 1               # 투명한 물체가 겹친 공간은 priority 가 높은 물체 (유리컵 > 물)
+ * absorption = [0.5, 0.1, 0.5]  # 안을 지나는 빛이 단위 거리마다 흡수되는 정도, 흡수되지 않은 색이 남음
+ * density = 2.0              # absorption 에 곱함 (짙은 액체일수록 크게)
+ *
+ * 여러 물체가 같이 쓰는 재질은 이름을 붙여 한 번만 정의하고 이름으로 참조
+ *
+ * [materials.glass]
+ * transparency = 1.0
+ *
+ * [[objects]]
+ * type = "sphere"
+ * material = "glass"
+ *
+ * 텍스처 경로는 장면 파일이 있는 디렉터리 기준의 상대 경로
+ */
+pub struct Scene {
+    pub camera: Camera,
+    pub lights: Vec<Light>,
+    pub objects: Vec<Box<dyn Hittable>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SceneDesc {
+    camera: Option<toml::Spanned<CameraDesc>>,
+    #[serde(default)]
+    materials: BTreeMap<String, toml::Spanned<MaterialDesc>>,
+    #[serde(default)]
+    lights: Vec<toml::Spanned<LightDesc>>,
+    #[serde(default)]
+    objects: Vec<toml::Spanned<ObjectDesc>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum ProjectionType {
+    #[default]
+    Perspective,
+    Orthographic,
+    Panoramic,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct CameraDesc {
+    pos: Option<[f32; 3]>,
+    look_at: Option<[f32; 3]>,
+    up: Option<[f32; 3]>,
+    fov: Option<f32>,
+    #[serde(default)]
+    projection: ProjectionType,
+    ortho_height: Option<f32>,
+    aspect: Option<f32>,
+    #[serde(default)]
+    aperture: f32,
+    focus_distance: Option<f32>,
+    #[serde(default)]
+    blades: u32,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum LightType {
+    #[default]
+    Point,
+    Directional,
+    Spot,
+    Rect,
+    Disk,
+    Sphere,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LightDesc {
+    #[serde(rename = "type", default)]
+    light_type: LightType,
+    pos: Option<[f32; 3]>,
+    dir: Option<[f32; 3]>,
+    cone_angle: Option<f32>,
+    #[serde(default)]
+    soft_edge: f32,
+    corner: Option<[f32; 3]>,
+    edge_u: Option<[f32; 3]>,
+    edge_v: Option<[f32; 3]>,
+    radius: Option<f32>,
+    #[serde(default = "default_light_samples")]
+    samples: u32,
+    #[serde(default = "default_light_color")]
+    color: [f32; 3],
+    #[serde(default = "default_light_intensity")]
+    intensity: f32,
+    #[serde(default = "default_light_attenuation")]
+    attenuation: [f32; 3],
+}
+
+fn default_light_color() -> [f32; 3] {
+    [1.0, 1.0, 1.0]
+}
+
+fn default_light_intensity() -> f32 {
+    std::f32::consts::PI
+}
+
+fn default_light_samples() -> u32 {
+    16
+}
+
+fn default_light_attenuation() -> [f32; 3] {
+    [0.0, 0.0, 1.0]
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+enum ObjectDesc {
+    Sphere {
+        center: [f32; 3],
+        radius: f32,
+        #[serde(default)]
+        material: MaterialRef,
+    },
+    Triangle {
+        vertices: [[f32; 3]; 3],
+        #[serde(default = "default_triangle_uv")]
+        uv: [[f32; 2]; 3],
+        #[serde(default)]
+        material: MaterialRef,
+    },
+    Square {
+        vertices: [[f32; 3]; 4],
+        #[serde(default = "default_square_uv")]
+        uv: [[f32; 2]; 4],
+        #[serde(default)]
+        material: MaterialRef,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MaterialDesc {
+    #[serde(default)]
+    model: ModelType,
+    #[serde(default)]
+    amb: [f32; 3],
+    #[serde(default)]
+    diff: [f32; 3],
+    #[serde(default)]
+    spec: [f32; 3],
+    #[serde(default)]
+    ks: f32,
+    #[serde(default = "default_alpha")]
+    alpha: f32,
+    #[serde(default)]
+    reflection: f32,
+    #[serde(default)]
+    transparency: f32,
+    amb_tex: Option<String>,
+    dif_tex: Option<String>,
+    #[serde(default = "default_ior")]
+    ior: f32,
+    #[serde(default)]
+    fresnel: FresnelType,
+    #[serde(default)]
+    priority: u32,
+    #[serde(default)]
+    absorption: [f32; 3],
+    #[serde(default = "default_density")]
+    density: f32,
+    base_color: Option<[f32; 3]>,
+    metallic: Option<f32>,
+    roughness: Option<f32>,
+    specular: Option<f32>,
+}
+
+#[derive(Deserialize, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+enum ModelType {
+    #[default]
+    Phong,
+    MetallicRoughness,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum FresnelType {
+    #[default]
+    Exact,
+    Schlick,
+}
+
+// 재질 이름 ("glass") 이나 재질 테이블
+enum MaterialRef {
+    Named(String),
+    Inline(MaterialDesc),
+}
+
+impl Default for MaterialRef {
+    fn default() -> Self {
+        MaterialRef::Inline(MaterialDesc::default())
+    }
+}
+
+impl<'de> Deserialize<'de> for MaterialRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MaterialRefVisitor;
+
+        impl<'de> Visitor<'de> for MaterialRefVisitor {
+            type Value = MaterialRef;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a material name or a material table")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<MaterialRef, E> {
+                Ok(MaterialRef::Named(name.to_string()))
+            }
+
+            // 테이블은 MaterialDesc 로 그대로 넘겨서 알 수 없는 필드 등의 오류 메시지를 유지
+            fn visit_map<M: MapAccess<'de>>(self, map: M) -> Result<MaterialRef, M::Error> {
+                MaterialDesc::deserialize(de::value::MapAccessDeserializer::new(map)).map(MaterialRef::Inline)
+            }
+        }
+
+        deserializer.deserialize_any(MaterialRefVisitor)
+    }
+}
+
+fn default_alpha() -> f32 {
+    1.0
+}
+
+fn default_ior() -> f32 {
+    1.5
+}
+
+fn default_density() -> f32 {
+    1.0
+}
+
+impl Default for MaterialDesc {
+    fn default() -> Self {
+        MaterialDesc {
+            model: ModelType::default(),
+            amb: [0.0; 3],
+            diff: [0.0; 3],
+            spec: [0.0; 3],
+            ks: 0.0,
+            alpha: default_alpha(),
+            reflection: 0.0,
+            transparency: 0.0,
+            amb_tex: None,
+            dif_tex: None,
+            ior: default_ior(),
+            fresnel: FresnelType::default(),
+            priority: 0,
+            absorption: [0.0; 3],
+            density: default_density(),
+            base_color: None,
+            metallic: None,
+            roughness: None,
+            specular: None,
+        }
+    }
+}
+
+fn default_triangle_uv() -> [[f32; 2]; 3] {
+    [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]
+}
+
+fn default_square_uv() -> [[f32; 2]; 4] {
+    [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]
+}
+
+fn vec3(v: [f32; 3]) -> cgmath::Vector3<f32> {
+    cgmath::vec3(v[0], v[1], v[2])
+}
+
+fn vec2(v: [f32; 2]) -> cgmath::Vector2<f32> {
+    cgmath::vec2(v[0], v[1])
+}
+
+// byte offset -> 1부터 시작하는 줄 번호
+fn line_of(src: &str, offset: usize) -> usize {
+    src[..offset.min(src.len())].matches('\n').count() + 1
+}
+
+impl MaterialDesc {
+    fn validate(&self) -> anyhow::Result<()> {
+        for (name, value) in [("reflection", self.reflection), ("transparency", self.transparency)] {
+            if !(0.0..=1.0).contains(&value) {
+                bail!("{} must be in [0, 1], got {}", name, value);
+            }
+        }
+
+        if self.reflection + self.transparency > 1.0 {
+            bail!("reflection + transparency must not exceed 1");
+        }
+
+        if self.alpha < 0.0 {
+            bail!("alpha must not be negative, got {}", self.alpha);
+        }
+
+        if self.ior.is_nan() || self.ior <= 0.0 {
+            bail!("ior must be positive, got {}", self.ior);
+        }
+
+        for value in self.absorption.into_iter().chain([self.density]) {
+            if !value.is_finite() || value < 0.0 {
+                bail!("absorption and density must not be negative, got {}", value);
+            }
+        }
+
+        if self.model == ModelType::Phong && (self.base_color.is_some() || self.metallic.is_some() || self.specular.is_some()) {
+            bail!("base_color, metallic and specular need model = \"metallic-roughness\"");
+        }
+
+        for (name, value) in [("metallic", self.metallic), ("roughness", self.roughness), ("specular", self.specular)] {
+            if let Some(value) = value {
+                if !(0.0..=1.0).contains(&value) {
+                    bail!("{} must be in [0, 1], got {}", name, value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// 같은 이미지 파일은 한 번만 읽어서 공유
+struct TextureCache<'a> {
+    base_dir: &'a Path,
+    textures: HashMap<PathBuf, Arc<Texture>>,
+}
+
+impl TextureCache<'_> {
+    fn get(&mut self, name: &Option<String>) -> anyhow::Result<Option<Arc<Texture>>> {
+        let Some(name) = name else {
+            return Ok(None);
+        };
+
+        let path = self.base_dir.join(name);
+
+        if let Some(texture) = self.textures.get(&path) {
+            return Ok(Some(texture.clone()));
+        }
+
+        let texture = Arc::new(Texture::load(&path)?);
+        self.textures.insert(path, texture.clone());
+
+        Ok(Some(texture))
+    }
+}
+
+impl Scene {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Scene> {
+        let path = path.as_ref();
+        let src = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read scene {}", path.display()))?;
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+
+        Scene::parse(&src, base_dir).with_context(|| format!("invalid scene {}", path.display()))
+    }
+
+    // base_dir: 텍스처 상대 경로의 기준 디렉터리
+    pub fn parse(src: &str, base_dir: &Path) -> anyhow::Result<Scene> {
+        // 문법 오류는 toml 에서 줄/칸 번호를 포함해서 알려줌
+        let desc: SceneDesc = toml::from_str(src).map_err(|e| anyhow!("{}", e))?;
+
+        let mut lights = Vec::new();
+
+        for light in desc.lights.iter() {
+            let line = line_of(src, light.span().start);
+
+            let built = Scene::build_light(light.get_ref())
+                .with_context(|| format!("light at line {}", line))?;
+
+            lights.push(built);
+        }
+
+        let mut textures = TextureCache { base_dir, textures: HashMap::new() };
+
+        let mut materials = HashMap::new();
+
+        for (name, material) in desc.materials.iter() {
+            let line = line_of(src, material.span().start);
+
+            let built = Scene::build_material(material.get_ref(), &mut textures)
+                .with_context(|| format!("material '{}' at line {}", name, line))?;
+
+            materials.insert(name.clone(), built);
+        }
+
+        let mut objects: Vec<Box<dyn Hittable>> = Vec::new();
+
+        for object in desc.objects.iter() {
+            let line = line_of(src, object.span().start);
+
+            let built = Scene::build_object(object.get_ref(), line, &materials, &mut textures)
+                .with_context(|| format!("object at line {}", line))?;
+
+            objects.push(built);
+        }
+
+        let camera = match &desc.camera {
+            Some(camera) => {
+                let line = line_of(src, camera.span().start);
+
+                Scene::build_camera(camera.get_ref()).with_context(|| format!("camera at line {}", line))?
+            }
+            None => Scene::build_camera(&CameraDesc::default())?,
+        };
+
+        Ok(Scene {
+            camera,
+            lights,
+            objects,
+        })
+    }
+
+    // 생략한 값은 Camera::default() 를 따름
+    fn build_camera(desc: &CameraDesc) -> anyhow::Result<Camera> {
+        let default = Camera::default();
+
+        let pos = desc.pos.map_or(default.pos, vec3);
+        let look_at = desc.look_at.map_or(default.look_at, vec3);
+
+        let projection = match desc.projection {
+            ProjectionType::Perspective => Projection::Perspective,
+            ProjectionType::Orthographic => {
+                let height = desc.ortho_height.context("orthographic projection needs ortho_height")?;
+                if height <= 0.0 {
+                    bail!("ortho_height must be positive, got {}", height);
+                }
+                Projection::Orthographic { height }
+            }
+            ProjectionType::Panoramic => Projection::Panoramic,
+        };
+
+        let camera = Camera {
+            pos,
+            look_at,
+            up: desc.up.map_or(default.up, vec3),
+            fov: desc.fov.unwrap_or(default.fov),
+            projection,
+            aspect: desc.aspect,
+            aperture: desc.aperture,
+            focus_distance: desc.focus_distance.unwrap_or((look_at - pos).magnitude()),
+            blades: desc.blades,
+        };
+
+        if camera.pos == camera.look_at {
+            bail!("pos and look_at must differ");
+        }
+        if (camera.look_at - camera.pos).cross(camera.up).magnitude() <= 0.0 {
+            bail!("up must not be parallel to the view direction");
+        }
+        if !(camera.fov > 0.0 && camera.fov < 180.0) {
+            bail!("fov must be in (0, 180), got {}", camera.fov);
+        }
+        if camera.aspect.is_some_and(|a| a <= 0.0) {
+            bail!("aspect must be positive");
+        }
+        if camera.aperture < 0.0 {
+            bail!("aperture must not be negative, got {}", camera.aperture);
+        }
+        if camera.focus_distance <= 0.0 {
+            bail!("focus_distance must be positive, got {}", camera.focus_distance);
+        }
+        if camera.blades == 1 || camera.blades == 2 {
+            bail!("blades must be 0 (round) or at least 3, got {}", camera.blades);
+        }
+
+        Ok(camera)
+    }
+
+    fn build_light(desc: &LightDesc) -> anyhow::Result<Light> {
+        let pos = || desc.pos.map(vec3).context("missing pos");
+        let dir = || {
+            let dir = desc.dir.context("missing dir")?;
+            if dir == [0.0; 3] {
+                bail!("dir must not be zero");
+            }
+            Ok(vec3(dir))
+        };
+
+        let radius = || {
+            let radius = desc.radius.context("missing radius")?;
+            if radius <= 0.0 {
+                bail!("radius must be positive, got {}", radius);
+            }
+            Ok(radius)
+        };
+
+        let kind = match desc.light_type {
+            LightType::Point => LightKind::Point { pos: pos()? },
+            LightType::Directional => LightKind::Directional { dir: dir()? },
+            LightType::Spot => {
+                let cone_angle = desc.cone_angle.context("missing cone_angle")?;
+
+                if !(0.0..=180.0).contains(&cone_angle) {
+                    bail!("cone_angle must be in [0, 180], got {}", cone_angle);
+                }
+                if desc.soft_edge < 0.0 || desc.soft_edge > cone_angle {
+                    bail!("soft_edge must be in [0, cone_angle], got {}", desc.soft_edge);
+                }
+
+                LightKind::Spot { pos: pos()?, dir: dir()?, cone_angle, soft_edge: desc.soft_edge }
+            }
+            LightType::Rect => {
+                let corner = desc.corner.context("missing corner")?;
+                let edge_u = vec3(desc.edge_u.context("missing edge_u")?);
+                let edge_v = vec3(desc.edge_v.context("missing edge_v")?);
+
+                if edge_u.cross(edge_v).magnitude() <= 0.0 {
+                    bail!("edge_u and edge_v must span a non-empty rectangle");
+                }
+
+                LightKind::Rect { corner: vec3(corner), edge_u, edge_v }
+            }
+            LightType::Disk => LightKind::Disk { center: pos()?, normal: dir()?, radius: radius()? },
+            LightType::Sphere => LightKind::Sphere { center: pos()?, radius: radius()? },
+        };
+
+        if desc.samples == 0 {
+            bail!("samples must be at least 1");
+        }
+
+        if desc.intensity < 0.0 {
+            bail!("intensity must not be negative, got {}", desc.intensity);
+        }
+        if desc.attenuation.iter().any(|a| *a < 0.0) || desc.attenuation == [0.0; 3] {
+            bail!("attenuation must be non-negative and not all zero");
+        }
+
+        Ok(Light {
+            kind,
+            color: vec3(desc.color),
+            intensity: desc.intensity,
+            attenuation: vec3(desc.attenuation),
+            samples: desc.samples,
+        })
+    }
+
+    fn build_material(desc: &MaterialDesc, textures: &mut TextureCache) -> anyhow::Result<Arc<Material>> {
+        desc.validate()?;
+
+        let (shading, roughness) = match desc.model {
+            ModelType::Phong => (Shading::Phong, desc.roughness.unwrap_or(0.0)),
+            ModelType::MetallicRoughness => {
+                let default = MetallicRoughness::default();
+                let roughness = desc.roughness.unwrap_or(default.roughness);
+
+                let shading = Shading::MetallicRoughness(MetallicRoughness {
+                    base_color: desc.base_color.map_or(default.base_color, vec3),
+                    metallic: desc.metallic.unwrap_or(default.metallic),
+                    roughness,
+                    specular: desc.specular.unwrap_or(default.specular),
+                });
+
+                (shading, roughness)
+            }
+        };
+
+        Ok(Arc::new(Material {
+            shading,
+            amb: vec3(desc.amb),
+            diff: vec3(desc.diff),
+            spec: vec3(desc.spec),
+            ks: desc.ks,
+            alpha: desc.alpha,
+            amb_tex: textures.get(&desc.amb_tex)?,
+            dif_tex: textures.get(&desc.dif_tex)?,
+            reflection: desc.reflection,
+            transparency: desc.transparency,
+            roughness,
+            ior: desc.ior,
+            fresnel: match desc.fresnel {
+                FresnelType::Exact => Fresnel::Exact,
+                FresnelType::Schlick => Fresnel::Schlick,
+            },
+            priority: desc.priority,
+            absorption: vec3(desc.absorption) * desc.density,
+        }))
+    }
+
+    // line: 이름으로 참조한 재질이 없을 때 알려줄 물체의 줄 번호
+    fn build_object(
+        desc: &ObjectDesc,
+        line: usize,
+        materials: &HashMap<String, Arc<Material>>,
+        textures: &mut TextureCache,
+    ) -> anyhow::Result<Box<dyn Hittable>> {
+        let material = |material: &MaterialRef, textures: &mut TextureCache| match material {
+            MaterialRef::Named(name) => materials.get(name).cloned().with_context(|| format!("unknown material '{}' at line {}", name, line)),
+            MaterialRef::Inline(desc) => Scene::build_material(desc, textures),
+        };
+
+        match desc {
+            ObjectDesc::Sphere { center, radius, material: material_ref } => {
+                if *radius <= 0.0 {
+                    bail!("sphere radius must be positive, got {}", radius);
+                }
+
+                let mut sphere = Sphere::new(vec3(*center), *radius);
+                sphere.material = material(material_ref, textures)?;
+
+                Ok(Box::new(sphere))
+            }
+            ObjectDesc::Triangle { vertices, uv, material: material_ref } => {
+                let mut triangle = Triangle::new(
+                    vec3(vertices[0]), vec3(vertices[1]), vec3(vertices[2]),
+                    vec2(uv[0]), vec2(uv[1]), vec2(uv[2]),
+                );
+                triangle.material = material(material_ref, textures)?;
+
+                Ok(Box::new(triangle))
+            }
+            ObjectDesc::Square { vertices, uv, material: material_ref } => {
+                let mut square = Square::new(
+                    vec3(vertices[0]), vec3(vertices[1]), vec3(vertices[2]), vec3(vertices[3]),
+                    vec2(uv[0]), vec2(uv[1]), vec2(uv[2]), vec2(uv[3]),
+                );
+                square.material = material(material_ref, textures)?;
+
+                Ok(Box::new(square))
+            }
+        }
+    }
+}
@@ -1,22 +1,18 @@
+use std::f32::consts::PI;
+use std::sync::Arc;
+
 use cgmath::InnerSpace;
+use crate::aabb::Aabb;
 use crate::ray::Ray;
-use crate::hit::{Hit, Hittable, LightProperty, Object, TextureProperty};
-use crate::texture::Texture;
+use crate::hit::{Hit, Hittable};
+use crate::material::Material;
 
 pub struct Sphere {
     // geometry properties
     pub center: cgmath::Vector3<f32>,
     pub radius: f32,
 
-    // light properties
-    pub amb: cgmath::Vector3<f32>, // ambient
-    pub diff: cgmath::Vector3<f32>, // diffuse
-    pub spec: cgmath::Vector3<f32>, // specular
-    pub ks: f32,
-    pub alpha: f32,
-
-    pub reflection: f32, // 0 ~ 1
-    pub transparency: f32, // 0 ~ 1
+    pub material: Arc<Material>,
 }
 
 impl Sphere {
@@ -24,19 +20,13 @@ impl Sphere {
         Sphere {
             center,
             radius,
-            amb: cgmath::Vector3::new(0.0, 0.0, 0.0),
-            diff: cgmath::Vector3::new(0.0, 0.0, 0.0),
-            spec: cgmath::Vector3::new(0.0, 0.0, 0.0),
-            ks: 0.0,
-            alpha: 1.0,
-            reflection: 0.0,
-            transparency: 0.0,
+            material: Arc::default(),
         }
     }
 }
 
 impl Hittable for Sphere {
-    fn intersect_ray_collision(&self, ray: &Ray) -> Hit {
+    fn intersect_ray_collision(&self, ray: &Ray) -> Hit<'_> {
         // Wikipedia Line–sphere intersection
         // https://en.wikipedia.org/wiki/Line%E2%80%93sphere_intersection
         let mut hit = Hit::new(-1.0, cgmath::vec3(0.0, 0.0, 0.0), cgmath::vec3(0.0, 0.0, 0.0));
@@ -65,34 +55,20 @@ impl Hittable for Sphere {
             hit.d = d;
             hit.point = point;
             hit.normal = normal;
-        }
-
-        hit
-    }
 
-    fn get_light_color_properties(&self) -> LightProperty {
-        LightProperty {
-            amb: self.amb,
-            diff: self.diff,
-            spec: self.spec,
-            ks: self.ks,
-            alpha: self.alpha
+            // 텍스처 좌표 (경도, 위도)
+            hit.uv = cgmath::vec2(
+                0.5 + normal.z.atan2(normal.x) / (2.0 * PI),
+                0.5 - normal.y.clamp(-1.0, 1.0).asin() / PI,
+            );
+            hit.material = Some(&self.material);
         }
-    }
-
-    fn get_ambient_texture(&self) -> &Option<Texture> {
-        &None
-    }
 
-    fn get_diffuse_texture(&self) -> &Option<Texture> {
-        &None
-    }
-
-    fn get_reflection(&self) -> f32 {
-        self.reflection
+        hit
     }
 
-    fn get_transparency(&self) -> f32 {
-        self.transparency
+    fn bounding_box(&self) -> Aabb {
+        let r = cgmath::vec3(self.radius, self.radius, self.radius);
+        Aabb::new(self.center - r, self.center + r)
     }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use std::ops::Mul;
 use std::path::Path;
+use anyhow::Context;
 use image;
-use image::{GenericImageView, ImageBuffer, Rgb};
 
 #[derive(Debug, Clone)]
 pub struct Texture {
@@ -13,17 +13,22 @@ pub struct Texture {
 
 impl Texture {
     pub fn new(file_name: &str) -> Self {
-        let img_path = Path::new(file_name);
-        let img = image::open(&img_path).expect("Failed to open image");
+        Texture::load(file_name).expect("Failed to open image")
+    }
+
+    pub fn load(file_name: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let img_path = file_name.as_ref();
+        let img = image::open(img_path)
+            .with_context(|| format!("failed to open image {}", img_path.display()))?;
 
         println!("image information: {}, {}, {}", img.width(), img.height(), img.color().channel_count());
 
-        Texture {
+        Ok(Texture {
             width: img.width() as i32,
             height: img.height() as i32,
             channels: img.color().channel_count(),
             image: img.into_bytes().clone()
-        }
+        })
 
         // 가상의 이미지 생성 테스트 코드
         // let mut img = ImageBuffer::new(4, 4);
@@ -120,7 +125,7 @@ impl Texture {
 
         let index = (x + (self.width as usize) * y) * self.channels as usize;
 
-        let r: f32 = self.image[index + 0] as f32 / 255.0;
+        let r: f32 = self.image[index] as f32 / 255.0;
         let g: f32 = self.image[index + 1] as f32 / 255.0;
         let b: f32 = self.image[index + 2] as f32 / 255.0;
 
@@ -142,7 +147,7 @@ impl Texture {
 
         let index = (x + (self.width as usize) * y) * self.channels as usize;
 
-        let r: f32 = self.image[index + 0] as f32 / 255.0;
+        let r: f32 = self.image[index] as f32 / 255.0;
         let g: f32 = self.image[index + 1] as f32 / 255.0;
         let b: f32 = self.image[index + 2] as f32 / 255.0;
 
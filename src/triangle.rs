@@ -1,8 +1,10 @@
+use std::sync::Arc;
+
 use cgmath::InnerSpace;
-use wgpu::naga::VectorSize::Tri;
-use crate::hit::{Hit, Hittable, LightProperty, Object, TextureProperty};
+use crate::hit::{Hit, Hittable};
+use crate::aabb::Aabb;
+use crate::material::Material;
 use crate::ray::Ray;
-use crate::texture::Texture;
 
 pub struct Triangle {
     pub v0: cgmath::Vector3<f32>,
@@ -13,15 +15,7 @@ pub struct Triangle {
     pub uv1: cgmath::Vector2<f32>,
     pub uv2: cgmath::Vector2<f32>,
 
-    // light properties
-    pub amb: cgmath::Vector3<f32>, // ambient
-    pub diff: cgmath::Vector3<f32>, // diffuse
-    pub spec: cgmath::Vector3<f32>, // specular
-    pub ks: f32,
-    pub alpha: f32,
-
-    pub reflection: f32, // 0 ~ 1
-    pub transparency: f32, // 0 ~ 1
+    pub material: Arc<Material>,
 }
 
 impl Triangle {
@@ -36,17 +30,12 @@ impl Triangle {
             uv0,
             uv1,
             uv2,
-            amb: cgmath::Vector3::new(0.0, 0.0, 0.0),
-            diff: cgmath::Vector3::new(0.0, 0.0, 0.0),
-            spec: cgmath::Vector3::new(0.0, 0.0, 0.0),
-            ks: 0.0,
-            alpha: 1.0,
-            reflection: 0.0,
-            transparency: 0.0,
+            material: Arc::default(),
         }
     }
 
     // https://www.scratchapixel.com/lessons/3d-basic-rendering/ray-tracing-rendering-a-triangle/ray-triangle-intersection-geometric-solution.html
+    #[allow(clippy::too_many_arguments)]
     fn intersect_ray_triangle(
         &self,
         start: cgmath::Vector3<f32>,
@@ -107,8 +96,8 @@ impl Triangle {
 
         let area_sum = area0 + area1 + area2;
 
-        *point = p.clone();
-        *face_normal = n.clone();
+        *point = p;
+        *face_normal = n;
         *t = d;
 
         *w0 = area0 / area_sum;
@@ -119,7 +108,7 @@ impl Triangle {
 }
 
 impl Hittable for Triangle {
-    fn intersect_ray_collision(&self, ray: &Ray) -> Hit {
+    fn intersect_ray_collision(&self, ray: &Ray) -> Hit<'_> {
         let mut hit = Hit::new(-1.0, cgmath::vec3(0.0, 0.0, 0.0), cgmath::vec3(0.0, 0.0, 0.0));
 
         let mut point = cgmath::vec3(0.0, 0.0, 0.0);
@@ -152,34 +141,13 @@ impl Hittable for Triangle {
             // Barycentric coordinates 확인용
             // println!("{:} {:}", w0, w1);
             hit.w = cgmath::vec2(w0, w1);
+            hit.material = Some(&self.material);
         }
 
         hit
     }
 
-    fn get_light_color_properties(&self) -> LightProperty {
-        LightProperty {
-            amb: self.amb,
-            diff: self.diff,
-            spec: self.spec,
-            ks: self.ks,
-            alpha: self.alpha
-        }
-    }
-
-    fn get_ambient_texture(&self) -> &Option<Texture> {
-        &None
-    }
-
-    fn get_diffuse_texture(&self) -> &Option<Texture> {
-        &None
-    }
-
-    fn get_reflection(&self) -> f32 {
-        self.reflection
-    }
-
-    fn get_transparency(&self) -> f32 {
-        self.transparency
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(&[self.v0, self.v1, self.v2])
     }
 }
\ No newline at end of file
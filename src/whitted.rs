@@ -0,0 +1,259 @@
+use std::f32::consts::PI;
+
+use cgmath::{ElementWise, InnerSpace};
+
+use crate::integrator::Integrator;
+use crate::light::LightKind;
+use crate::material::Shading;
+use crate::medium::MediumStack;
+use crate::microfacet;
+use crate::occlusion::AmbientOcclusion;
+use crate::optics;
+use crate::path::next_visible_hit;
+use crate::ray::Ray;
+use crate::raytracer::Raytracer;
+use crate::sampler::SampleStream;
+use crate::sampling::stratify;
+
+// Whitted 스타일 ray tracing
+// 표면마다 조명은 Phong 으로 계산하고, 거울 반사와 굴절만 재귀적으로 따라감 (간접광 없음)
+#[derive(Debug, Clone, Copy)]
+pub struct Whitted {
+    pub max_depth: i32, // 반사/굴절을 따라가는 최대 횟수
+
+    // 거친 (roughness > 0) 반사/굴절에서 쏘는 광선 개수
+    // 카메라 광선이 따라가는 길에서 처음 만난 거친 표면이 모두 쓰고, 그 뒤로는 하나씩만 쏨
+    pub glossy_samples: u32,
+
+    // 있으면 amb 에 ambient occlusion 을 곱해서 구석과 틈의 amb 를 어둡게 함
+    pub ambient_occlusion: Option<AmbientOcclusion>,
+}
+
+impl Default for Whitted {
+    fn default() -> Self {
+        Whitted { max_depth: 5, glossy_samples: 8, ambient_occlusion: None }
+    }
+}
+
+impl Integrator for Whitted {
+    fn radiance(&self, raytracer: &Raytracer, ray: &Ray, samples: &mut SampleStream) -> cgmath::Vector3<f32> {
+        self.tracy_ray(raytracer, ray, self.max_depth, self.glossy_samples, &MediumStack::new(), samples)
+    }
+}
+
+impl Whitted {
+    // glossy_budget: 거친 표면에서 쏠 수 있는 광선 개수 (거친 표면을 지나면 1)
+    // media: 광선이 지금 지나가고 있는 투명한 물체들 (카메라에서 시작하면 비어있음)
+    // samples: 현재 픽셀 샘플에서 다음 차원을 꺼내 쓰는 곳
+    pub fn tracy_ray<'a>(
+        &self,
+        raytracer: &'a Raytracer,
+        ray: &Ray,
+        level: i32,
+        glossy_budget: u32,
+        media: &MediumStack<'a>,
+        samples: &mut SampleStream,
+    ) -> cgmath::Vector3<f32> {
+        let mut color = cgmath::vec3(0.0, 0.0, 0.0);
+        let mut phong_color = cgmath::vec3(0.0, 0.0, 0.0);
+
+        if level < 0 {
+            return color;
+        }
+
+        // 더 높은 priority 의 투명한 물체 안에 있는 경계면은 없는 것처럼 지나감
+        let next = next_visible_hit(raytracer, Ray { start: ray.start, dir: ray.dir }, media.clone(), true);
+        let (ray, hit, media) = (&next.ray, &next.hit, &next.media);
+
+        // 물체보다 가까이 있는 면적 조명은 직접 보임
+        if let Some((_, light)) = next.light {
+            return light.emission().mul_element_wise(next.transmittance);
+        }
+
+        if hit.d >= 0.0 {
+            if let Some(material) = hit.material {
+                let entering = ray.dir.dot(hit.normal) < 0.0;
+
+                let diff = material.diff;
+                let spec = material.spec;
+                let alpha = material.alpha;
+
+                let n = hit.normal.normalize();
+                let e = (-ray.dir).normalize();
+
+                // 모든 조명의 diffuse, specular 를 더함
+                let mut diffuse = cgmath::vec3(0.0, 0.0, 0.0);
+                let mut specular = cgmath::vec3(0.0, 0.0, 0.0);
+
+                // metallic-roughness 재질은 diffuse, specular 를 한 번에 계산
+                let mut microfacet = cgmath::vec3(0.0, 0.0, 0.0);
+                let tint = material.diffuse_texture(&hit.uv);
+
+                // l 방향에서 radiance 만큼 들어온 빛
+                let mut shade = |l: cgmath::Vector3<f32>, radiance: cgmath::Vector3<f32>| {
+                    if let Shading::MetallicRoughness(pbr) = &material.shading {
+                        microfacet += pbr.eval(n, e, l, tint).mul_element_wise(radiance);
+                        return;
+                    }
+
+                    // diffuse
+                    diffuse += (cgmath::dot(n, l).max(0.0) * diff).mul_element_wise(radiance);
+
+                    // specular
+                    let r = 2.0 * cgmath::dot(n, l) * n - l;
+
+                    specular += (spec * cgmath::dot(r, e).max(0.0).powf(alpha)).mul_element_wise(radiance);
+                };
+
+                for light in raytracer.lights().iter() {
+                    // 면적 조명은 조명 위 여러 점으로 그림자 광선을 쏴서 평균 (stratified sampling)
+                    let n_samples = light.sample_count();
+
+                    // photon 을 내보내는 조명은 유리를 지나온 빛을 caustic 으로 더하므로, 투명한 물체도 빛을 막음
+                    let caustics = raytracer.photon_map.is_some() && !matches!(light.kind, LightKind::Directional { .. });
+
+                    for k in 0..n_samples {
+                        let u = stratify(k, n_samples, samples.next_2d());
+
+                        let Some(light_sample) = light.sample(hit.point, u) else {
+                            continue;
+                        };
+                        let l = light_sample.dir;
+
+                        // shadow
+                        // 충돌 지점에서 조명 방향으로 광선을 쏴서 가려지는지 확인
+                        let shadow_ray = Ray { start: hit.point + (l * 1e-4f32), dir: l };
+                        let visibility = if caustics {
+                            if raytracer.find_any_collision(&shadow_ray, light_sample.distance) { 0.0 } else { 1.0 }
+                        } else {
+                            raytracer.light_transmission(&shadow_ray, light_sample.distance)
+                        };
+
+                        if visibility <= 0.0 {
+                            continue;
+                        }
+
+                        shade(l, light_sample.radiance * (visibility / n_samples as f32));
+                    }
+                }
+
+                // 거울, 유리를 지나 모인 빛 (caustic): 주변 photon 마다 flux / (원판 면적 * cos) 의 빛이 photon 이 온 방향에서 들어온 것으로 봄
+                if let Some(photon_map) = &raytracer.photon_map {
+                    let facing = if entering { n } else { -1.0 * n };
+                    let area = PI * photon_map.radius * photon_map.radius;
+
+                    photon_map.gather(hit.point, facing, |photon| {
+                        let cos = n.dot(photon.dir);
+
+                        if cos > 0.0 {
+                            shade(photon.dir, photon.power / (area * cos));
+                        }
+                    });
+                }
+
+                let reflection = material.reflection;
+                let transparency = material.transparency;
+
+                // texture calculation - ambient, diffuse
+                let mut ambient = material.ambient(&hit.uv);
+
+                if let Some(occlusion) = &self.ambient_occlusion {
+                    if ambient != cgmath::vec3(0.0, 0.0, 0.0) {
+                        let facing = if entering { n } else { -1.0 * n };
+                        ambient *= occlusion.estimate(raytracer, hit.point, facing, samples);
+                    }
+                }
+
+                phong_color += ambient;
+
+                // Phong 의 diff, spec 과 pbr.eval 은 PI 를 곱한 값이므로 PI 로 나눠서 path tracing 의 BSDF 와 밝기를 맞춤
+                // (흰 diffuse 면이 정면에서 intensity PI 인 조명을 받으면 밝기 1)
+                phong_color += (diffuse.mul_element_wise(tint) + specular + microfacet) / PI;
+
+                color += phong_color * (1.0 - reflection - transparency);
+
+                // 투명한 부분 (transparency) 은 경계면에서 Fresnel 식에 따라 반사와 굴절로 나뉨
+                // 비스듬히 볼수록 많이 반사되고, 전반사면 모두 반사
+
+                // 참고
+                // https://samdriver.xyz/article/refraction-sphere (그림들이 좋아요)
+                // https://www.scratchapixel.com/lessons/3d-basic-rendering/introduction-to-shading/reflection-refraction-fresnel (오류있음)
+                // https://web.cse.ohio-state.edu/~shen.94/681/Site/Slides_files/reflection_refraction.pdf (슬라이드가 보기 좋지는 않지만 정확해요)
+                if reflection > 0.0 || transparency > 0.0 {
+                    // 광선이 오는 쪽을 향한 법선
+                    let normal = if entering { hit.normal } else { -1.0 * hit.normal };
+
+                    // 굴절된 광선이 들어가는 매질과 굴절률 비율 (나가는 쪽 / 들어오는 쪽)
+                    let refraction = (transparency > 0.0).then(|| {
+                        // 밖에서 안에서 들어가는 경우 (예: 공기->유리, 물->유리)
+                        if entering {
+                            (material.ior / media.ior(), media.enter(material))
+                        }
+                        // 안에서 밖으로 나가는 경우 (예: 유리->공기, 유리->유리 안의 물)
+                        else {
+                            let inner = media.exit(material);
+                            (inner.ior() / material.ior, inner)
+                        }
+                    });
+
+                    // 거친 표면 (roughness > 0) 은 미세면 법선을 여러 개 골라서 각각 반사/굴절시킨 색을 평균 (glossy)
+                    // 튕길 때마다 광선 수가 곱해지지 않도록 남은 개수를 처음 만난 거친 표면에서 모두 쓰고 다음부터는 하나씩만 쏨
+                    // (거울 너머로 보이는 거친 표면도 카메라가 직접 본 것과 같은 개수)
+                    let glossy = material.roughness > 0.0;
+                    let alpha = microfacet::roughness_to_alpha(material.roughness);
+                    let rays = if glossy { glossy_budget.max(1) } else { 1 };
+                    let next_budget = if glossy { 1 } else { glossy_budget };
+                    let cos_v = -ray.dir.dot(normal);
+
+                    for _ in 0..rays {
+                        let m = if glossy {
+                            microfacet::sample_visible_normal(normal, -ray.dir, alpha, samples.next_2d())
+                        } else {
+                            normal
+                        };
+
+                        let mut reflect_weight = reflection;
+                        let mut refracted = None;
+
+                        if let Some((eta, inner)) = &refraction {
+                            let fresnel = material.fresnel.reflectance(-m.dot(ray.dir), *eta);
+
+                            // 전반사면 fresnel 이 1 이므로 모두 반사
+                            reflect_weight += transparency * fresnel;
+                            refracted = optics::refract(ray.dir, m, *eta).map(|dir| (dir, transparency * (1.0 - fresnel), inner));
+                        }
+
+                        let reflected_dir = optics::reflect(ray.dir, m);
+                        let cos_r = reflected_dir.dot(normal);
+
+                        // 미세면에서 반사된 방향이 표면 아래를 향하면 다른 미세면에 막힘
+                        if reflect_weight > 0.0 && cos_r > 0.0 {
+                            if glossy {
+                                reflect_weight *= microfacet::sampled_weight(cos_v, cos_r, alpha);
+                            }
+
+                            let reflected_ray = Ray { start: hit.point + (reflected_dir * 1e-4f32), dir: reflected_dir };
+                            color += self.tracy_ray(raytracer, &reflected_ray, level - 1, next_budget, media, samples) * (reflect_weight / rays as f32);
+                        }
+
+                        if let Some((refracted_dir, mut refract_weight, inner)) = refracted {
+                            let cos_t = -refracted_dir.dot(normal);
+
+                            if refract_weight > 0.0 && cos_t > 0.0 {
+                                if glossy {
+                                    refract_weight *= microfacet::sampled_weight(cos_v, cos_t, alpha);
+                                }
+
+                                let refracted_ray = Ray { start: hit.point + (refracted_dir * 1e-4f32), dir: refracted_dir };
+                                color += self.tracy_ray(raytracer, &refracted_ray, level - 1, next_budget, inner, samples) * (refract_weight / rays as f32);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // 투명한 물체 안을 지나온 광선은 지나온 거리만큼 흡수됨
+        color.mul_element_wise(next.transmittance)
+    }
+}
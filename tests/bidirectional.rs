@@ -0,0 +1,138 @@
+mod common;
+
+use common::{mean, render, render_with, FLOOR};
+use raytracing_rs::bidirectional::BidirectionalPathTracing;
+use raytracing_rs::path::PathTracing;
+
+// path tracing 과 같은 답: Le * (R / D)^2
+#[test]
+fn sphere_light_on_a_lambert_floor() {
+    let src = FLOOR.replace(
+        "LIGHT",
+        r#"
+[[lights]]
+type = "sphere"
+pos = [0.0, 1.5, 0.0]
+radius = 0.5
+intensity = 4.5
+"#,
+    );
+
+    let m = mean(&render(&src, Box::new(BidirectionalPathTracing::default()), 8, 32), 0) / 255.0;
+    assert!((m - 0.5).abs() < 0.03, "{}", m);
+}
+
+// 점 조명: albedo / PI * I / h^2 (조명 쪽 경로의 밝기도 거리에 따라 줄어듦)
+#[test]
+fn point_light_on_a_lambert_floor() {
+    let src = FLOOR.replace(
+        "LIGHT",
+        r#"
+[[lights]]
+type = "point"
+pos = [0.0, 2.0, 0.0]
+intensity = 6.2832
+attenuation = [0.0, 0.0, 1.0]
+"#,
+    );
+
+    let m = mean(&render(&src, Box::new(BidirectionalPathTracing::default()), 8, 16), 0) / 255.0;
+    assert!((m - 0.5).abs() < 0.03, "{}", m);
+}
+
+// 유리 구 가운데의 점 조명: 카메라 쪽에서는 조명에 닿을 수 없어서 path tracing 은 바닥이 검고,
+// light tracing 은 구를 수직으로 빠져나온 빛 (Fresnel 로 조금 줄어듦) 으로 유리가 없을 때와 거의 같게 바닥을 밝힘
+#[test]
+fn light_tracing_reaches_a_light_inside_glass() {
+    let light = r#"
+[[lights]]
+type = "point"
+pos = [0.0, 2.0, 0.0]
+intensity = 6.2832
+attenuation = [0.0, 0.0, 1.0]
+"#;
+    let glass = r#"
+[[objects]]
+type = "sphere"
+center = [0.0, 2.0, 0.0]
+radius = 0.3
+
+[objects.material]
+transparency = 1.0
+ior = 1.5
+"#;
+
+    // 조명에서 나간 빛이 화면에 자주 닿도록 넓게 봄
+    let open = FLOOR.replace("fov = 4.0", "fov = 30.0").replace("LIGHT", light);
+    let covered = format!("{}{}", open, glass);
+
+    let path = mean(&render(&covered, Box::new(PathTracing::default()), 16, 4), 0);
+    assert!(path < 1.0, "{}", path);
+
+    let expected = mean(&render(&open, Box::new(BidirectionalPathTracing::default()), 16, 16), 0);
+    let m = mean(&render(&covered, Box::new(BidirectionalPathTracing::default()), 16, 64), 0);
+    assert!(m > 0.85 * expected && m < 1.02 * expected, "{} (without glass {})", m, expected);
+}
+
+// 작은 방: 간접광까지 path tracing 과 같은 밝기
+#[test]
+fn matches_path_tracing_in_a_closed_box() {
+    let src = r#"
+[camera]
+pos = [0.0, 0.0, -0.9]
+look_at = [0.0, 0.0, 0.0]
+up = [0.0, 1.0, 0.0]
+fov = 60.0
+
+[[lights]]
+type = "rect"
+corner = [-0.3, 0.99, -0.3]
+edge_u = [0.6, 0.0, 0.0]
+edge_v = [0.0, 0.0, 0.6]
+intensity = 60.0
+
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 0.0]
+radius = 1.0
+
+[objects.material]
+diff = [0.6, 0.5, 0.4]
+"#;
+
+    let path = render(src, Box::new(PathTracing { max_depth: 4, ..PathTracing::default() }), 16, 32);
+    let bdpt = render(src, Box::new(BidirectionalPathTracing { max_depth: 4 }), 16, 32);
+
+    for channel in 0..3 {
+        let (a, b) = (mean(&path, channel), mean(&bdpt, channel));
+        assert!((a - b).abs() < 0.04 * a, "channel {}: path {}, bdpt {}", channel, a, b);
+    }
+}
+
+// 다른 픽셀에 더하는 빛 (splat) 도 스레드 수와 상관없이 같은 순서로 더해짐
+#[test]
+fn splats_do_not_depend_on_thread_count() {
+    let src = FLOOR.replace(
+        "LIGHT",
+        r#"
+[[lights]]
+type = "rect"
+corner = [-0.25, 1.0, -0.25]
+edge_u = [0.5, 0.0, 0.0]
+edge_v = [0.0, 0.0, 0.5]
+intensity = 2.0
+"#,
+    );
+
+    let render_threads = |threads| {
+        let mut raytracer = common::raytracer(&src, Box::new(BidirectionalPathTracing::default()), 24, 2);
+        raytracer.threads = threads;
+        render_with(&raytracer)
+    };
+
+    let single = render_threads(1);
+
+    for threads in [2, 5] {
+        assert!(render_threads(threads) == single, "{} threads differ", threads);
+    }
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use std::sync::Arc;
+
+use raytracing_rs::camera::Camera;
+use raytracing_rs::hit::Hittable;
+use raytracing_rs::material::Material;
+use raytracing_rs::light::Light;
+use raytracing_rs::raytracer::Raytracer;
+use raytracing_rs::scene::Scene;
+use raytracing_rs::sphere::Sphere;
+use raytracing_rs::triangle::Triangle;
+
+fn assert_same_image_as_brute_force(mut raytracer: Raytracer) {
+    raytracer.use_bvh = true;
+    let with_bvh = common::render_with(&raytracer);
+
+    raytracer.use_bvh = false;
+    let brute_force = common::render_with(&raytracer);
+
+    assert!(with_bvh == brute_force, "BVH image differs from brute-force image");
+}
+
+// 재현 가능한 난수 (LCG)
+fn next_f32(state: &mut u32) -> f32 {
+    *state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+    (*state >> 8) as f32 / (1u32 << 24) as f32
+}
+
+#[test]
+fn bvh_matches_brute_force_on_default_scene() {
+    let scene = Scene::load("./scenes/default.toml").unwrap();
+
+    assert_same_image_as_brute_force(common::scene_raytracer(scene, 64, 48));
+}
+
+#[test]
+fn bvh_matches_brute_force_on_triangle_soup() {
+    let mut state = 7;
+    let mut objects: Vec<Box<dyn Hittable>> = Vec::new();
+
+    for _ in 0..500 {
+        let center = cgmath::vec3(
+            next_f32(&mut state) * 4.0 - 2.0,
+            next_f32(&mut state) * 3.0 - 1.5,
+            next_f32(&mut state) * 4.0 + 0.5,
+        );
+        let mut corner = || cgmath::vec3(
+            next_f32(&mut state) - 0.5,
+            next_f32(&mut state) - 0.5,
+            next_f32(&mut state) - 0.5,
+        ) * 0.6;
+        let (a, b, c) = (corner(), corner(), corner());
+
+        let mut triangle = Triangle::new(
+            center + a, center + b, center + c,
+            cgmath::vec2(0.0, 0.0), cgmath::vec2(1.0, 0.0), cgmath::vec2(0.0, 1.0),
+        );
+        triangle.material = Arc::new(Material {
+            amb: cgmath::vec3(0.1, 0.1, 0.1),
+            diff: cgmath::vec3(next_f32(&mut state), next_f32(&mut state), next_f32(&mut state)),
+            ..Material::default()
+        });
+        objects.push(Box::new(triangle));
+    }
+
+    let mut sphere = Sphere::new(cgmath::vec3(0.0, 0.0, 2.0), 0.7);
+    sphere.material = Arc::new(Material {
+        diff: cgmath::vec3(1.0, 1.0, 1.0),
+        reflection: 0.5,
+        ..Material::default()
+    });
+    objects.push(Box::new(sphere));
+
+    let scene = Scene {
+        camera: Camera::default(),
+        lights: vec![Light::point(cgmath::vec3(1.0, 2.0, -1.0))],
+        objects,
+    };
+
+    assert_same_image_as_brute_force(common::scene_raytracer(scene, 64, 48));
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use cgmath::InnerSpace;
+
+use raytracing_rs::camera::{Camera, Projection};
+use raytracing_rs::random::Pcg32;
+use raytracing_rs::sampling::sample_polygon;
+
+// 조명 없이 ambient 만 흰색인 구 하나를 렌더링
+fn render_sphere(camera: Camera, center: cgmath::Vector3<f32>, width: u32, height: u32) -> image::RgbImage {
+    let scene = common::spheres(camera, vec![(center, 0.3, common::white())]);
+
+    common::render_with(&common::scene_raytracer(scene, width, height))
+}
+
+// 밝은 픽셀들의 중심 (픽셀 좌표)
+fn bright_centroid(img: &image::RgbImage) -> (f32, f32) {
+    let (mut sx, mut sy, mut n) = (0.0, 0.0, 0.0);
+
+    for (x, y, p) in img.enumerate_pixels() {
+        if p[0] > 128 {
+            sx += x as f32 + 0.5;
+            sy += y as f32 + 0.5;
+            n += 1.0;
+        }
+    }
+
+    assert!(n > 0.0, "sphere is not visible");
+    (sx / n, sy / n)
+}
+
+fn assert_near(actual: (f32, f32), expected: (f32, f32)) {
+    let d = ((actual.0 - expected.0).powi(2) + (actual.1 - expected.1).powi(2)).sqrt();
+    assert!(d < 1.0, "sphere center at {:?}, expected {:?}", actual, expected);
+}
+
+fn camera(projection: Projection) -> Camera {
+    Camera {
+        pos: cgmath::vec3(0.0, 0.0, 0.0),
+        look_at: cgmath::vec3(0.0, 0.0, 1.0),
+        fov: 60.0,
+        projection,
+        ..Camera::default()
+    }
+}
+
+#[test]
+fn perspective_projects_center_through_pinhole() {
+    let center = cgmath::vec3(1.0, 0.5, 5.0);
+    let img = render_sphere(camera(Projection::Perspective), center, 100, 80);
+
+    // 화면 좌표 = (x / z, y / z) / tan(fov / 2)
+    let half_h = 30.0f32.to_radians().tan();
+    let half_w = half_h * 100.0 / 80.0;
+    let expected = (
+        (1.0 + center.x / center.z / half_w) * 0.5 * 100.0,
+        (1.0 - center.y / center.z / half_h) * 0.5 * 80.0,
+    );
+
+    assert_near(bright_centroid(&img), expected);
+}
+
+#[test]
+fn orthographic_ignores_depth() {
+    let expected = (
+        (1.0 + 1.0 / 2.5) * 0.5 * 100.0,
+        (1.0 - 0.5 / 2.0) * 0.5 * 80.0,
+    );
+
+    // 세로 4, 가로 5 크기의 화면. 깊이가 달라도 같은 위치에 보여야 함
+    for z in [2.0, 8.0] {
+        let img = render_sphere(camera(Projection::Orthographic { height: 4.0 }), cgmath::vec3(1.0, 0.5, z), 100, 80);
+
+        assert_near(bright_centroid(&img), expected);
+    }
+}
+
+#[test]
+fn panoramic_maps_longitude_and_latitude() {
+    // 오른쪽 90°, 위쪽 30° 방향
+    let (lon, lat) = (90.0f32.to_radians(), 30.0f32.to_radians());
+    let center = cgmath::vec3(lat.cos() * lon.sin(), lat.sin(), lat.cos() * lon.cos()) * 5.0;
+
+    let img = render_sphere(camera(Projection::Panoramic), center, 180, 90);
+
+    // u = 0.5 + lon / 360°, v = 0.5 - lat / 180°
+    let expected = ((0.5 + 0.25) * 180.0, (0.5 - 30.0 / 180.0) * 90.0);
+
+    assert_near(bright_centroid(&img), expected);
+}
+
+// 초점 평면 위의 점은 렌즈 위 어디에서 출발한 광선이든 pinhole 광선과 같은 점을 지남 (같은 픽셀에 보임)
+#[test]
+fn thin_lens_focuses_on_the_focus_plane() {
+    let mut rng = Pcg32::new(3, 0);
+
+    for blades in [0, 6] {
+        let lens = Camera { aperture: 0.2, focus_distance: 4.0, blades, aspect: Some(1.0), ..camera(Projection::Perspective) };
+        let pinhole = Camera { aperture: 0.0, ..camera(Projection::Perspective) };
+
+        for uv in [cgmath::vec2(0.5, 0.5), cgmath::vec2(0.1, 0.8), cgmath::vec2(0.9, 0.2)] {
+            // pinhole 광선이 초점 평면 (z = 4) 에 닿는 점
+            let center = pinhole.generate_ray(uv, cgmath::vec2(0.5, 0.5));
+            let focus_point = center.start + center.dir * (4.0 / center.dir.z);
+
+            let mut spread = 0.0f32;
+            for _ in 0..64 {
+                let ray = lens.generate_ray(uv, rng.next_vec2());
+                let to_focus = focus_point - ray.start;
+
+                // 광선과 초점 평면 위의 점 사이의 거리
+                let miss = (to_focus - ray.dir * to_focus.dot(ray.dir)).magnitude();
+                assert!(miss < 1e-4, "blades {}, uv {:?}: {}", blades, uv, miss);
+
+                spread = spread.max((ray.start - center.start).magnitude());
+            }
+
+            // 렌즈 위 여러 곳에서 출발함
+            assert!(spread > 0.1, "{}", spread);
+        }
+    }
+}
+
+// 외접원 반지름 1 인 정 n 각형 안의 점: 모든 변의 안쪽 (중심에서 변까지의 거리는 cos(PI / n))
+#[test]
+fn polygon_samples_stay_inside_the_polygon() {
+    let mut rng = Pcg32::new(5, 0);
+
+    for n in 3..=8 {
+        let angle = 2.0 * PI / n as f32;
+        let mut sectors = vec![0; n as usize];
+
+        for _ in 0..2000 {
+            let p = sample_polygon(n, rng.next_vec2());
+
+            for k in 0..n {
+                let mid = (k as f32 + 0.5) * angle;
+                let m = cgmath::vec2(mid.cos(), mid.sin());
+                assert!(p.dot(m) <= (PI / n as f32).cos() + 1e-5, "n = {}: {:?} is outside edge {}", n, p, k);
+            }
+
+            let theta = p.y.atan2(p.x).rem_euclid(2.0 * PI);
+            sectors[((theta / angle) as usize).min(n as usize - 1)] += 1;
+        }
+
+        // 모든 삼각형에 고르게 퍼짐
+        for count in sectors {
+            assert!(count > 2000 / n / 2, "n = {}: {}", n, count);
+        }
+    }
+}
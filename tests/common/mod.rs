@@ -0,0 +1,95 @@
+// 여러 테스트에서 같이 쓰는 장면과 렌더링
+// 테스트 파일마다 일부만 쓰므로 쓰지 않는 것은 경고하지 않음
+#![allow(dead_code)]
+
+use std::path::Path;
+use std::sync::Arc;
+
+use raytracing_rs::camera::Camera;
+use raytracing_rs::hit::Hittable;
+use raytracing_rs::integrator::Integrator;
+use raytracing_rs::material::Material;
+use raytracing_rs::raytracer::Raytracer;
+use raytracing_rs::sampler::SamplerKind;
+use raytracing_rs::sampling::PixelSampling;
+use raytracing_rs::scene::Scene;
+use raytracing_rs::sphere::Sphere;
+
+// 위에서 비스듬히 내려다본 흰 바닥, 조명은 LIGHT 로 바꿈
+pub const FLOOR: &str = r#"
+[camera]
+pos = [0.0, 2.0, -2.0]
+look_at = [0.0, 0.0, 0.0]
+up = [0.0, 1.0, 0.0]
+fov = 4.0
+
+LIGHT
+
+[[objects]]
+type = "square"
+vertices = [[-10.0, 0.0, -10.0], [-10.0, 0.0, 10.0], [10.0, 0.0, 10.0], [10.0, 0.0, -10.0]]
+
+[objects.material]
+diff = [1.0, 1.0, 1.0]
+"#;
+
+// FLOOR 를 바로 위 (높이 2) 에서 수직으로 내려다봄 (화면 위쪽이 +z), lens 는 fov 대신 쓸 카메라 설정
+pub fn top_down_floor(lens: &str) -> String {
+    FLOOR
+        .replace("pos = [0.0, 2.0, -2.0]", "pos = [0.0, 2.0, 0.0]")
+        .replace("up = [0.0, 1.0, 0.0]", "up = [0.0, 0.0, 1.0]")
+        .replace("fov = 4.0", lens)
+}
+
+// 조명 없이도 흰색으로 보이는 재질 (amb 만)
+pub fn white() -> Material {
+    Material { amb: cgmath::vec3(1.0, 1.0, 1.0), ..Material::default() }
+}
+
+// 조명 없이 구 (center, radius, material) 만 있는 장면
+pub fn spheres(camera: Camera, spheres: Vec<(cgmath::Vector3<f32>, f32, Material)>) -> Scene {
+    let objects = spheres
+        .into_iter()
+        .map(|(center, radius, material)| {
+            let mut sphere = Sphere::new(center, radius);
+            sphere.material = Arc::new(material);
+            Box::new(sphere) as Box<dyn Hittable>
+        })
+        .collect();
+
+    Scene { camera, lights: vec![], objects }
+}
+
+pub fn parse(src: &str) -> Scene {
+    Scene::parse(src, Path::new(".")).unwrap()
+}
+
+// width x height 화면, 나머지는 Raytracer::new 의 기본값 (Whitted, 픽셀 가운데 한 샘플)
+pub fn scene_raytracer(scene: Scene, width: u32, height: u32) -> Raytracer {
+    Raytracer::new(width as i32, height as i32, scene)
+}
+
+// size x size 화면, 픽셀마다 spp 개의 샘플을 픽셀 전체에서 고름 (Sobol)
+pub fn raytracer(src: &str, integrator: Box<dyn Integrator>, size: u32, spp: u32) -> Raytracer {
+    let mut raytracer = scene_raytracer(parse(src), size, size);
+    raytracer.integrator = integrator;
+    raytracer.samples_per_pixel = spp;
+    raytracer.pixel_sampling = PixelSampling::Random;
+    raytracer.sampler = SamplerKind::Sobol.create(0);
+    raytracer
+}
+
+pub fn render_with(raytracer: &Raytracer) -> image::RgbImage {
+    let mut img_buff = image::RgbImage::new(raytracer.width as u32, raytracer.height as u32);
+    raytracer.render(&mut img_buff);
+    img_buff
+}
+
+pub fn render(src: &str, integrator: Box<dyn Integrator>, size: u32, spp: u32) -> image::RgbImage {
+    render_with(&raytracer(src, integrator, size, spp))
+}
+
+// 한 채널의 평균 (0 ~ 255)
+pub fn mean(img_buff: &image::RgbImage, channel: usize) -> f32 {
+    img_buff.pixels().map(|p| p[channel] as f32).sum::<f32>() / (img_buff.width() * img_buff.height()) as f32
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{mean, render};
+use raytracing_rs::integrator::{DebugView, Integrator, IntegratorKind};
+use raytracing_rs::occlusion::AmbientOcclusion;
+use raytracing_rs::ray::Ray;
+use raytracing_rs::raytracer::Raytracer;
+use raytracing_rs::sampler::SampleStream;
+use raytracing_rs::whitted::Whitted;
+
+// 위에서 내려다본 색 있는 바닥 (조명 없음)
+fn floor() -> String {
+    common::top_down_floor("fov = 30.0").replace("LIGHT", "").replace("diff = [1.0, 1.0, 1.0]", "diff = [0.2, 0.4, 0.6]")
+}
+
+// 카메라와 바닥 사이, 아래를 향한 판 (카메라 쪽에서는 뒷면이라 보이지 않음)
+fn covered(floor: &str) -> String {
+    format!(
+        "{}{}",
+        floor,
+        r#"
+[[objects]]
+type = "square"
+vertices = [[-10.0, 0.1, -10.0], [10.0, 0.1, -10.0], [10.0, 0.1, 10.0], [-10.0, 0.1, 10.0]]
+"#
+    )
+}
+
+// Raytracer 밖에서 만든 integrator 도 그대로 쓸 수 있음
+struct Constant(cgmath::Vector3<f32>);
+
+impl Integrator for Constant {
+    fn radiance(&self, _raytracer: &Raytracer, _ray: &Ray, _samples: &mut SampleStream) -> cgmath::Vector3<f32> {
+        self.0
+    }
+}
+
+#[test]
+fn custom_integrator_colors_every_pixel() {
+    let img_buff = render(&floor(), Box::new(Constant(cgmath::vec3(1.0, 0.5, 0.0))), 8, 1);
+
+    for p in img_buff.pixels() {
+        assert_eq!(p.0, [255, 127, 0]);
+    }
+}
+
+#[test]
+fn integrator_names() {
+    assert_eq!("whitted".parse::<IntegratorKind>().unwrap(), IntegratorKind::Whitted);
+    assert_eq!("path".parse::<IntegratorKind>().unwrap(), IntegratorKind::Path);
+    assert_eq!("normal".parse::<IntegratorKind>().unwrap(), IntegratorKind::Debug(DebugView::Normal));
+
+    let err = "photon".parse::<IntegratorKind>().unwrap_err();
+    assert!(err.to_string().contains("unknown integrator 'photon'"), "{}", err);
+}
+
+#[test]
+fn debug_views_show_the_first_hit() {
+    // 법선 (0, 1, 0) -> (0.5, 1, 0.5)
+    for p in render(&floor(), IntegratorKind::Debug(DebugView::Normal).create(), 8, 1).pixels() {
+        assert_eq!(p.0, [127, 255, 127]);
+    }
+
+    for p in render(&floor(), IntegratorKind::Debug(DebugView::Albedo).create(), 8, 1).pixels() {
+        assert_eq!(p.0, [51, 102, 153]);
+    }
+
+    // 카메라 바로 아래가 가장 가까움
+    let depth = render(&floor(), IntegratorKind::Debug(DebugView::Depth).create(), 8, 1);
+    assert!(depth.get_pixel(4, 4)[0] > depth.get_pixel(0, 0)[0]);
+    assert!(depth.get_pixel(4, 4)[0] <= 255 / 3 + 1);
+}
+
+// 트인 바닥은 가리는 것이 없고, 바로 위에 판을 놓으면 distance 안에서는 전부 가려짐
+#[test]
+fn ambient_occlusion_of_an_open_and_a_covered_floor() {
+    let ao = AmbientOcclusion { samples: 8, distance: 0.5 };
+
+    for p in render(&floor(), Box::new(ao), 8, 1).pixels() {
+        assert_eq!(p.0, [255, 255, 255]);
+    }
+
+    let m = mean(&render(&covered(&floor()), Box::new(ao), 8, 1), 0);
+    assert!(m < 40.0, "{}", m);
+}
+
+// amb 만 있는 바닥: 트인 곳은 그대로, 덮인 곳은 ambient occlusion 만큼 어두워짐
+#[test]
+fn ambient_occlusion_darkens_the_ambient_term() {
+    let floor = floor().replace("diff = [0.2, 0.4, 0.6]", "amb = [1.0, 1.0, 1.0]\ndiff = [0.0, 0.0, 0.0]");
+    let ao = AmbientOcclusion { samples: 8, distance: 0.5 };
+    let whitted = Whitted { ambient_occlusion: Some(ao), ..Whitted::default() };
+
+    for p in render(&floor, Box::new(whitted), 8, 1).pixels() {
+        assert_eq!(p.0, [255, 255, 255]);
+    }
+
+    let plain = render(&covered(&floor), Box::new(Whitted::default()), 8, 1);
+    assert!(plain.pixels().all(|p| p.0 == [255, 255, 255]));
+
+    let m = mean(&render(&covered(&floor), Box::new(whitted), 8, 1), 0);
+    assert!(m < 40.0, "{}", m);
+}
@@ -0,0 +1,262 @@
+mod common;
+
+use cgmath::InnerSpace;
+
+use raytracing_rs::light::{Light, LightKind};
+use raytracing_rs::random::Pcg32;
+use raytracing_rs::sampling::stratify;
+
+fn render(src: &str, width: u32, height: u32) -> image::RgbImage {
+    common::render_with(&common::scene_raytracer(common::parse(src), width, height))
+}
+
+// 위에서 내려다본 흰 바닥과 바로 위의 점 조명 (감쇠 없음): 바닥의 밝기는 cos * intensity / PI = cos * 0.8
+fn lit_floor() -> String {
+    common::top_down_floor("fov = 10.0").replace(
+        "LIGHT",
+        r#"
+[[lights]]
+type = "point"
+pos = [0.0, 1.0, 0.0]
+intensity = 2.5133
+attenuation = [1.0, 0.0, 0.0]
+"#,
+    )
+}
+
+// 조명과 바닥 사이, 아래를 향한 판 (카메라 쪽에서는 뒷면이라 보이지 않고 그림자만 만듦)
+fn with_occluder(transparency: f32) -> String {
+    format!(
+        "{}{}transparency = {:.1}\n",
+        lit_floor(),
+        r#"
+[[objects]]
+type = "square"
+vertices = [[-1.0, 0.5, -1.0], [1.0, 0.5, -1.0], [1.0, 0.5, 1.0], [-1.0, 0.5, 1.0]]
+
+[objects.material]
+"#,
+        transparency
+    )
+}
+
+#[test]
+fn opaque_occluder_casts_a_shadow() {
+    let lit = render(&lit_floor(), 8, 8);
+    let shadowed = render(&with_occluder(0.0), 8, 8);
+
+    assert!(lit.get_pixel(4, 4)[0] >= 200, "{:?}", lit.get_pixel(4, 4));
+    assert_eq!(shadowed.get_pixel(4, 4).0, [0, 0, 0]);
+}
+
+#[test]
+fn half_transparent_occluder_lets_half_the_light_through() {
+    let lit = render(&lit_floor(), 8, 8).get_pixel(4, 4)[0] as f32;
+    let shadowed = render(&with_occluder(0.5), 8, 8).get_pixel(4, 4)[0] as f32;
+
+    assert!((shadowed / lit - 0.5).abs() < 0.02, "{} / {}", shadowed, lit);
+}
+
+fn light(kind: LightKind, attenuation: cgmath::Vector3<f32>) -> Light {
+    Light { kind, color: cgmath::vec3(1.0, 0.5, 0.25), intensity: 2.0, attenuation, samples: 1 }
+}
+
+// 원점 아래에서 축과 angle (degree) 만큼 벌어진, 조명에서 거리 2 인 점
+fn below(angle: f32) -> cgmath::Vector3<f32> {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    cgmath::vec3(2.0 * sin, -2.0 * cos, 0.0)
+}
+
+const U: cgmath::Vector2<f32> = cgmath::vec2(0.5, 0.5);
+
+#[test]
+fn point_light_falls_off_with_attenuation() {
+    let light = light(LightKind::Point { pos: cgmath::vec3(0.0, 0.0, 0.0) }, cgmath::vec3(1.0, 0.5, 0.25));
+
+    // 1 / (1 + 0.5 * 2 + 0.25 * 4) = 1 / 3
+    let sample = light.sample(cgmath::vec3(0.0, -2.0, 0.0), U).unwrap();
+    assert_eq!(sample.dir, cgmath::vec3(0.0, 1.0, 0.0));
+    assert_eq!(sample.distance, 2.0);
+    assert!((sample.radiance - cgmath::vec3(2.0, 1.0, 0.5) / 3.0).magnitude() < 1e-6, "{:?}", sample.radiance);
+
+    // 조명과 같은 위치에서는 방향이 없음 (NaN 이 아님)
+    assert!(light.sample(cgmath::vec3(0.0, 0.0, 0.0), U).is_none());
+}
+
+#[test]
+fn directional_light_does_not_fall_off() {
+    let light = light(LightKind::Directional { dir: cgmath::vec3(0.0, -2.0, 0.0) }, cgmath::vec3(1.0, 0.5, 0.25));
+
+    for point in [cgmath::vec3(0.0, 0.0, 0.0), cgmath::vec3(100.0, -50.0, 3.0)] {
+        let sample = light.sample(point, U).unwrap();
+        assert_eq!(sample.dir, cgmath::vec3(0.0, 1.0, 0.0));
+        assert_eq!(sample.distance, f32::MAX);
+        assert_eq!(sample.radiance, cgmath::vec3(2.0, 1.0, 0.5));
+    }
+}
+
+fn spot(cone_angle: f32, soft_edge: f32) -> Light {
+    light(
+        LightKind::Spot { pos: cgmath::vec3(0.0, 0.0, 0.0), dir: cgmath::vec3(0.0, -1.0, 0.0), cone_angle, soft_edge },
+        cgmath::vec3(0.0, 0.0, 1.0),
+    )
+}
+
+// 원뿔 안에서는 점 조명과 같고 (1 / d^2), 밖은 빛이 없음
+#[test]
+fn spot_light_is_cut_off_at_the_cone() {
+    let light = spot(30.0, 0.0);
+
+    for angle in [0.0, 20.0, 29.0] {
+        let sample = light.sample(below(angle), U).unwrap();
+        assert!((sample.radiance - cgmath::vec3(2.0, 1.0, 0.5) / 4.0).magnitude() < 1e-6, "{}: {:?}", angle, sample.radiance);
+    }
+
+    assert!(light.sample(below(31.0), U).is_none());
+    assert!(light.sample(below(90.0), U).is_none());
+    assert!(light.sample(cgmath::vec3(0.0, 0.0, 0.0), U).is_none());
+}
+
+// soft_edge 안에서는 cos 에 대한 smoothstep 으로 줄어듦
+#[test]
+fn spot_light_soft_edge_is_a_smoothstep() {
+    let light = spot(30.0, 10.0);
+    let factor = |angle: f32| light.sample(below(angle), U).map_or(0.0, |s| s.radiance.x * 4.0 / 2.0);
+
+    assert!((factor(0.0) - 1.0).abs() < 1e-5);
+    assert!((factor(20.0) - 1.0).abs() < 1e-5);
+    assert_eq!(factor(30.5), 0.0);
+
+    let (cos_outer, cos_inner) = (30f32.to_radians().cos(), 20f32.to_radians().cos());
+    for angle in [21.0f32, 25.0, 29.0] {
+        let t = (angle.to_radians().cos() - cos_outer) / (cos_inner - cos_outer);
+        let expected = t * t * (3.0 - 2.0 * t);
+        assert!((factor(angle) - expected).abs() < 1e-4, "{}: {} / {}", angle, factor(angle), expected);
+    }
+
+    assert!(factor(22.0) > factor(25.0) && factor(25.0) > factor(28.0));
+}
+
+// 다각형 조명이 point (법선 n) 에 주는 cos 가중 입체각 (Lambert 의 공식)
+fn polygon_irradiance(point: cgmath::Vector3<f32>, n: cgmath::Vector3<f32>, vertices: &[cgmath::Vector3<f32>]) -> f32 {
+    let mut sum = 0.0;
+
+    for i in 0..vertices.len() {
+        let a = (vertices[i] - point).normalize();
+        let b = (vertices[(i + 1) % vertices.len()] - point).normalize();
+
+        sum += a.dot(b).clamp(-1.0, 1.0).acos() * a.cross(b).normalize().dot(n);
+    }
+
+    sum.abs() / 2.0
+}
+
+// 격자로 나누어떨어지지 않는 샘플 수에서도 stratify 한 면적 조명의 평균이 정확한 값과 같음
+#[test]
+fn stratified_rect_light_estimate_is_unbiased() {
+    let (corner, edge_u, edge_v) = (cgmath::vec3(-0.5, 1.0, -0.5), cgmath::vec3(1.0, 0.0, 0.0), cgmath::vec3(0.0, 0.0, 1.0));
+    let light = Light {
+        kind: LightKind::Rect { corner, edge_u, edge_v },
+        color: cgmath::vec3(1.0, 1.0, 1.0),
+        intensity: 1.0,
+        attenuation: cgmath::vec3(1.0, 0.0, 0.0),
+        samples: 1,
+    };
+
+    // 조명 한쪽 아래의 점
+    let point = cgmath::vec3(0.7, 0.0, 0.2);
+    let up = cgmath::vec3(0.0, 1.0, 0.0);
+    let expected = polygon_irradiance(point, up, &[corner, corner + edge_u, corner + edge_u + edge_v, corner + edge_v]);
+
+    for n in [3, 5] {
+        let mut rng = Pcg32::new(1, n as u64);
+        let trials = 20000;
+        let mut sum = 0.0;
+
+        for _ in 0..trials {
+            for k in 0..n {
+                let sample = light.sample(point, stratify(k, n, rng.next_vec2())).unwrap();
+                sum += sample.radiance.x * sample.dir.dot(up) / n as f32;
+            }
+        }
+
+        let estimate = sum / trials as f32;
+        assert!((estimate - expected).abs() < 0.005 * expected, "n = {}: {} / {}", n, estimate, expected);
+    }
+}
+
+// 위에서 수직으로 내려다본 바닥의 가로 2 x 세로 0.125 띠 (64 x 4 픽셀)
+// 높이 0.5 의 판이 x < 0 쪽을 가리고, 조명은 LIGHT 로 바꿈 (가운데가 높이 1.5)
+fn penumbra_floor() -> String {
+    common::top_down_floor("projection = \"orthographic\"\northo_height = 0.125")
+}
+
+// 아래를 향한 판 (카메라에서는 뒷면이라 보이지 않음)
+const HALF_PLANE: &str = r#"
+[[objects]]
+type = "square"
+vertices = [[-10.0, 0.5, -10.0], [0.0, 0.5, -10.0], [0.0, 0.5, 10.0], [-10.0, 0.5, 10.0]]
+
+[objects.material]
+"#;
+
+// 그림자가 있을 때와 없을 때의 밝기 비율이 0 과 1 사이인 부분의 너비 (world 단위)
+fn penumbra_width(light: &str) -> f32 {
+    let scene = penumbra_floor().replace("LIGHT", light);
+    let lit = render(&scene, 64, 4);
+    let shadowed = render(&format!("{}{}", scene, HALF_PLANE), 64, 4);
+
+    let partial = (0..64)
+        .filter(|&i| {
+            let ratio = shadowed.get_pixel(i, 2)[0] as f32 / lit.get_pixel(i, 2)[0] as f32;
+            ratio > 0.02 && ratio < 0.98
+        })
+        .count();
+
+    partial as f32 * 2.0 / 64.0
+}
+
+// 조명의 폭 w 가 만드는 반그림자의 너비는 w * (판의 높이) / (조명과 판의 높이 차이) = w / 2
+#[test]
+fn area_light_penumbra_width() {
+    let rect = |w: f32| {
+        format!(
+            "[[lights]]\ntype = \"rect\"\ncorner = [{}, 1.5, {}]\nedge_u = [{}, 0.0, 0.0]\nedge_v = [0.0, 0.0, {}]\nintensity = 4.7\nsamples = 256\n",
+            -w / 2.0, -w / 2.0, w, w
+        )
+    };
+    let disk = "[[lights]]\ntype = \"disk\"\npos = [0.0, 1.5, 0.0]\ndir = [0.0, -1.0, 0.0]\nradius = 0.5\nintensity = 6.3\nsamples = 256\n";
+    // 구는 카메라에서 보이지 않게 띠 옆으로 옮김 (판의 경계와 평행한 방향이라 너비는 같음)
+    let sphere = "[[lights]]\ntype = \"sphere\"\npos = [0.0, 1.5, 1.0]\nradius = 0.5\nintensity = 6.3\nsamples = 256\n";
+
+    for (name, light, expected) in [("rect", rect(1.0), 0.5), ("small rect", rect(0.5), 0.25), ("disk", disk.to_string(), 0.5), ("sphere", sphere.to_string(), 0.5)] {
+        let width = penumbra_width(&light);
+        assert!(width > 0.8 * expected && width < 1.1 * expected, "{}: {} / {}", name, width, expected);
+    }
+}
+
+// 카메라에서 바로 보이는 면적 조명은 color * intensity, rect 와 disk 는 뒤쪽에서 보이지 않음
+#[test]
+fn area_light_seen_directly_by_the_camera() {
+    let lights = [
+        "type = \"rect\"\ncorner = [-0.5, 0.0, -0.5]\nedge_u = [1.0, 0.0, 0.0]\nedge_v = [0.0, 0.0, 1.0]",
+        "type = \"disk\"\npos = [0.0, 0.0, 0.0]\ndir = [0.0, -1.0, 0.0]\nradius = 0.5",
+        "type = \"sphere\"\npos = [0.0, 0.0, 0.0]\nradius = 0.5",
+    ];
+
+    for light in lights {
+        let view = |y: f32| {
+            format!(
+                "[camera]\npos = [0.0, {}, 0.0]\nlook_at = [0.0, 0.0, 0.0]\nup = [0.0, 0.0, 1.0]\nfov = 10.0\n\n[[lights]]\n{}\ncolor = [1.0, 0.5, 0.25]\nintensity = 0.8\n",
+                y, light
+            )
+        };
+
+        let below = render(&view(-2.0), 8, 8);
+        assert_eq!(below.get_pixel(4, 4).0, [204, 102, 51], "{}", light);
+
+        let above = render(&view(2.0), 8, 8);
+        let expected = if light.contains("sphere") { [204, 102, 51] } else { [0, 0, 0] };
+        assert_eq!(above.get_pixel(4, 4).0, expected, "{}", light);
+    }
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use raytracing_rs::camera::Camera;
+use raytracing_rs::material::Material;
+use raytracing_rs::medium::MediumStack;
+
+fn dielectric(ior: f32, priority: u32) -> Material {
+    Material { transparency: 1.0, ior, priority, ..Material::default() }
+}
+
+// 흰 바닥과 검은 배경 앞의 투명한 구들
+fn render(spheres: Vec<(cgmath::Vector3<f32>, f32, Material)>) -> image::RgbImage {
+    let floor = (cgmath::vec3(0.0, -101.0, 2.0), 100.0, common::white());
+    let scene = common::spheres(Camera::default(), std::iter::once(floor).chain(spheres).collect());
+
+    common::render_with(&common::scene_raytracer(scene, 48, 48))
+}
+
+fn max_difference(a: &image::RgbImage, b: &image::RgbImage) -> u8 {
+    a.pixels().zip(b.pixels()).flat_map(|(p, q)| (0..3).map(move |c| p[c].abs_diff(q[c]))).max().unwrap()
+}
+
+fn glass() -> (cgmath::Vector3<f32>, f32, Material) {
+    (cgmath::vec3(0.0, 0.0, 2.0), 0.8, dielectric(1.5, 1))
+}
+
+#[test]
+fn stack_uses_highest_priority_medium() {
+    let glass = dielectric(1.5, 2);
+    let water = dielectric(1.33, 1);
+
+    let air = MediumStack::new();
+    assert_eq!(air.ior(), 1.0);
+
+    let in_glass = air.enter(&glass);
+    assert_eq!(in_glass.ior(), 1.5);
+
+    // 유리 안에서 만난 물의 경계면은 보이지 않음
+    assert!(!in_glass.is_boundary_visible(&water, true));
+
+    let in_both = in_glass.enter(&water);
+    assert_eq!(in_both.ior(), 1.5);
+    assert!(in_both.is_boundary_visible(&glass, false));
+
+    // 유리를 나가면 물 안
+    let in_water = in_both.exit(&glass);
+    assert_eq!(in_water.ior(), 1.33);
+    assert!(in_water.is_boundary_visible(&water, false));
+    assert_eq!(in_water.exit(&water).ior(), 1.0);
+}
+
+#[test]
+fn lower_priority_object_inside_glass_is_invisible() {
+    let alone = render(vec![glass()]);
+
+    // 유리보다 priority 가 낮은 물은 유리 안에서 보이지 않음
+    let water = (cgmath::vec3(0.1, -0.1, 2.0), 0.4, dielectric(1.33, 0));
+    let with_water = render(vec![glass(), water]);
+
+    assert!(max_difference(&alone, &with_water) <= 2);
+}
+
+#[test]
+fn bubble_refracts_against_the_surrounding_glass() {
+    let alone = render(vec![glass()]);
+
+    // 유리와 굴절률이 같은 공은 경계면에서 굴절/반사가 거의 없음 (밖이 공기라고 가정하면 보임)
+    // 경계면을 지날 때마다 재귀 깊이를 쓰므로 여러 번 반사된 빛만 조금 달라짐
+    let same_ior = (cgmath::vec3(0.1, -0.1, 2.0), 0.4, dielectric(1.5, 1));
+    assert!(max_difference(&alone, &render(vec![glass(), same_ior])) <= 16);
+
+    // 공기 방울은 유리 -> 공기 굴절률로 보임
+    let bubble = (cgmath::vec3(0.1, -0.1, 2.0), 0.4, dielectric(1.0, 2));
+    assert!(max_difference(&alone, &render(vec![glass(), bubble])) > 50);
+}
+
+// 흰색 ambient 로 된 큰 구 안에서, 빨간색만 흡수하는 유리 구의 가운데를 봄
+fn absorbing_sphere_center(radius: f32) -> image::Rgb<u8> {
+    let room = (cgmath::vec3(0.0, 0.0, 0.0), 20.0, common::white());
+    let glass = (cgmath::vec3(0.0, 0.0, 3.0), radius, Material { absorption: cgmath::vec3(1.0, 0.0, 0.0), ..dielectric(1.5, 0) });
+    let scene = common::spheres(Camera::default(), vec![room, glass]);
+
+    *common::render_with(&common::scene_raytracer(scene, 33, 33)).get_pixel(16, 16)
+}
+
+#[test]
+fn transmittance_decays_exponentially_with_distance() {
+    let glass = Material { absorption: cgmath::vec3(1.0, 0.5, 0.0), ..dielectric(1.5, 0) };
+
+    assert_eq!(MediumStack::new().transmittance(10.0), cgmath::vec3(1.0, 1.0, 1.0));
+
+    let t = MediumStack::new().enter(&glass).transmittance(2.0);
+    assert!((t.x - (-2.0f32).exp()).abs() < 1e-6);
+    assert!((t.y - (-1.0f32).exp()).abs() < 1e-6);
+    assert_eq!(t.z, 1.0);
+}
+
+#[test]
+fn thick_glass_absorbs_more_than_thin_glass() {
+    let thick = absorbing_sphere_center(1.0);
+    let thin = absorbing_sphere_center(0.25);
+
+    // 가운데를 지나는 거리는 지름, 투과율 exp(-2) 와 exp(-0.5)
+    assert!(thick[0].abs_diff((255.0 * (-2.0f32).exp()) as u8) <= 15, "{:?}", thick);
+    assert!(thin[0].abs_diff((255.0 * (-0.5f32).exp()) as u8) <= 15, "{:?}", thin);
+
+    // 흡수하지 않는 색은 그대로 통과
+    for p in [thick, thin] {
+        assert!(p[1] >= 245 && p[2] >= 245, "{:?}", p);
+    }
+}
@@ -0,0 +1,248 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use cgmath::InnerSpace;
+use raytracing_rs::microfacet::{ggx_distribution, roughness_to_alpha, sample_visible_normal, sampled_weight, MetallicRoughness};
+use raytracing_rs::optics::reflect;
+use raytracing_rs::whitted::Whitted;
+
+// 반구 위의 방향들에 대해 f(방향) * dω 를 더함 (θ, φ 격자)
+fn integrate_hemisphere(f: impl Fn(cgmath::Vector3<f32>) -> f32) -> f32 {
+    let (n_theta, n_phi) = (400, 200);
+    let d_theta = 0.5 * PI / n_theta as f32;
+    let d_phi = 2.0 * PI / n_phi as f32;
+
+    let mut sum = 0.0;
+
+    for i in 0..n_theta {
+        let theta = (i as f32 + 0.5) * d_theta;
+
+        for j in 0..n_phi {
+            let phi = (j as f32 + 0.5) * d_phi;
+            let dir = cgmath::vec3(theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos());
+
+            sum += f(dir) * theta.sin() * d_theta * d_phi;
+        }
+    }
+
+    sum
+}
+
+#[test]
+fn ggx_distribution_is_normalized() {
+    // 투영된 미세면의 넓이는 1
+    for alpha in [0.1, 0.3, 0.6, 1.0] {
+        let area = integrate_hemisphere(|h| ggx_distribution(h.z, alpha) * h.z);
+        assert!((area - 1.0).abs() < 0.02, "alpha {}: {}", alpha, area);
+    }
+}
+
+#[test]
+fn white_surfaces_do_not_create_energy() {
+    let n = cgmath::vec3(0.0, 0.0, 1.0);
+    let white = cgmath::vec3(1.0, 1.0, 1.0);
+
+    for metallic in [0.0, 1.0] {
+        for roughness in [0.2, 0.5, 1.0] {
+            let material = MetallicRoughness { base_color: white, metallic, roughness, specular: 0.5 };
+
+            for cos_v in [1.0f32, 0.7, 0.3] {
+                let v = cgmath::vec3((1.0 - cos_v * cos_v).sqrt(), 0.0, cos_v);
+
+                // eval 은 PI 를 곱한 값
+                let albedo = integrate_hemisphere(|l| material.eval(n, v, l, white).x / PI);
+                assert!(albedo <= 1.01, "metallic {} roughness {} cos {}: {}", metallic, roughness, cos_v, albedo);
+
+                // Smith 모델은 미세면 사이에서 여러 번 튕기는 빛을 무시하므로 거칠수록 어두워지지만,
+                // 매끈한 금속은 거의 모두 반사
+                if metallic == 1.0 && roughness == 0.2 {
+                    assert!(albedo >= 0.9, "roughness {} cos {}: {}", roughness, cos_v, albedo);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn rough_white_dielectric_matches_phong_diffuse() {
+    // F0 = 0 이면 정면에서는 diffuse 만 남고, Phong 의 diff = 1 과 같은 밝기
+    let material = MetallicRoughness {
+        base_color: cgmath::vec3(1.0, 1.0, 1.0),
+        metallic: 0.0,
+        roughness: 1.0,
+        specular: 0.0,
+    };
+
+    let n = cgmath::vec3(0.0, 0.0, 1.0);
+    let c = material.eval(n, n, n, cgmath::vec3(1.0, 1.0, 1.0));
+    assert!((c.x - 1.0).abs() < 1e-5, "{:?}", c);
+
+    // 조명이 뒤에 있으면 0
+    let behind = material.eval(n, n, -n, cgmath::vec3(1.0, 1.0, 1.0));
+    assert_eq!(behind, cgmath::vec3(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn metals_reflect_their_base_color() {
+    let gold = MetallicRoughness {
+        base_color: cgmath::vec3(1.0, 0.8, 0.3),
+        metallic: 1.0,
+        roughness: 0.3,
+        specular: 0.5,
+    };
+
+    let n = cgmath::vec3(0.0, 0.0, 1.0);
+    let v = cgmath::vec3(0.6, 0.0, 0.8);
+    let mirror = cgmath::vec3(-0.6, 0.0, 0.8);
+
+    let c = gold.eval(n, v, mirror, cgmath::vec3(1.0, 1.0, 1.0));
+    assert!(c.x > c.y && c.y > c.z, "{:?}", c);
+
+    // 하이라이트에서 멀어지면 어두워짐
+    let off = gold.eval(n, v, cgmath::vec3(0.6, 0.0, 0.8), cgmath::vec3(1.0, 1.0, 1.0));
+    assert!(off.x < c.x * 0.1, "{:?} vs {:?}", off, c);
+}
+
+#[test]
+fn visible_normal_sampling_matches_the_brdf() {
+    // F = 1 인 흰 금속이면, 반사된 방향마다 무게를 곱한 평균이 BRDF 를 적분한 반사율과 같아야 함
+    let n = cgmath::vec3(0.0, 0.0, 1.0);
+    let white = cgmath::vec3(1.0, 1.0, 1.0);
+
+    for roughness in [0.2, 0.5, 0.9] {
+        let alpha = roughness_to_alpha(roughness);
+        let material = MetallicRoughness { base_color: white, metallic: 1.0, roughness, specular: 0.5 };
+
+        for cos_v in [1.0f32, 0.5] {
+            let v = cgmath::vec3((1.0 - cos_v * cos_v).sqrt(), 0.0, cos_v);
+            let albedo = integrate_hemisphere(|l| material.eval(n, v, l, white).x / PI);
+
+            let k = 256;
+            let mut sum = 0.0;
+
+            for i in 0..k {
+                for j in 0..k {
+                    let u = cgmath::vec2((i as f32 + 0.5) / k as f32, (j as f32 + 0.5) / k as f32);
+                    let m = sample_visible_normal(n, v, alpha, u);
+
+                    assert!((m.magnitude() - 1.0).abs() < 1e-4 && m.dot(v) >= -1e-4, "{:?}", m);
+
+                    let l = reflect(-v, m);
+                    if l.dot(n) > 0.0 {
+                        sum += sampled_weight(cos_v, l.dot(n), alpha);
+                    }
+                }
+            }
+
+            let estimate = sum / (k * k) as f32;
+            assert!((estimate - albedo).abs() < 0.02, "roughness {} cos {}: {} vs {}", roughness, cos_v, estimate, albedo);
+        }
+    }
+}
+
+// 왼쪽 절반만 흰 벽 앞에 투명한 구를 두고, 구를 통해 보이는 벽의 경계가 흐려진 정도 (중간 밝기 픽셀 수)
+fn blurred_edge_pixels(roughness: f32) -> usize {
+    let src = format!(
+        r#"
+[camera]
+pos = [0.0, 0.0, -1.0]
+look_at = [0.0, 0.0, 0.0]
+up = [0.0, 1.0, 0.0]
+fov = 40.0
+
+[[objects]]
+type = "square"
+vertices = [[-10.0, 10.0, 5.0], [0.0, 10.0, 5.0], [0.0, -10.0, 5.0], [-10.0, -10.0, 5.0]]
+
+[objects.material]
+amb = [1.0, 1.0, 1.0]
+
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 2.0]
+radius = 1.0
+
+[objects.material]
+transparency = 1.0
+roughness = {}
+"#,
+        roughness
+    );
+
+    let mut raytracer = common::scene_raytracer(common::parse(&src), 64, 64);
+    raytracer.samples_per_pixel = 4;
+    raytracer.integrator = Box::new(Whitted { glossy_samples: 16, ..Whitted::default() });
+
+    let img_buff = common::render_with(&raytracer);
+
+    (16..48).filter(|&x| (40..215).contains(&img_buff.get_pixel(x, 32)[0])).count()
+}
+
+#[test]
+fn rough_glass_blurs_what_is_behind_it() {
+    let sharp = blurred_edge_pixels(0.0);
+    let frosted = blurred_edge_pixels(0.4);
+
+    assert!(sharp <= 3, "{}", sharp);
+    assert!(frosted >= 8, "{}", frosted);
+}
+
+// 굴절률 1 인 유리판 너머로 보여도 거친 유리에서 카메라가 직접 볼 때와 같은 개수의 광선을 쏨
+fn frosted_sphere(pane: bool) -> image::RgbImage {
+    let pane = if pane {
+        r#"
+[[objects]]
+type = "square"
+vertices = [[-10.0, 10.0, 0.0], [10.0, 10.0, 0.0], [10.0, -10.0, 0.0], [-10.0, -10.0, 0.0]]
+
+[objects.material]
+transparency = 1.0
+ior = 1.0
+"#
+    } else {
+        ""
+    };
+
+    let src = format!(
+        r#"
+[camera]
+pos = [0.0, 0.0, -1.0]
+look_at = [0.0, 0.0, 0.0]
+up = [0.0, 1.0, 0.0]
+fov = 40.0
+
+[[objects]]
+type = "square"
+vertices = [[-10.0, 10.0, 5.0], [0.0, 10.0, 5.0], [0.0, -10.0, 5.0], [-10.0, -10.0, 5.0]]
+
+[objects.material]
+amb = [1.0, 1.0, 1.0]
+
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 2.0]
+radius = 1.0
+
+[objects.material]
+transparency = 1.0
+roughness = 0.4
+{}"#,
+        pane
+    );
+
+    let mut raytracer = common::scene_raytracer(common::parse(&src), 64, 64);
+    raytracer.integrator = Box::new(Whitted { glossy_samples: 64, ..Whitted::default() });
+
+    common::render_with(&raytracer)
+}
+
+#[test]
+fn glossy_samples_are_spent_behind_smooth_glass() {
+    let direct = frosted_sphere(false);
+    let through_pane = frosted_sphere(true);
+
+    let diff = direct.pixels().zip(through_pane.pixels()).map(|(a, b)| (a[0] as f32 - b[0] as f32).abs()).sum::<f32>() / (64 * 64) as f32;
+
+    assert!(diff < 20.0, "{}", diff);
+}
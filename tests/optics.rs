@@ -0,0 +1,104 @@
+mod common;
+
+use cgmath::InnerSpace;
+use raytracing_rs::camera::Camera;
+use raytracing_rs::material::Material;
+use raytracing_rs::optics::{fresnel_dielectric, fresnel_schlick, reflect, refract, Fresnel};
+
+#[test]
+fn normal_incidence_reflects_four_percent_of_glass() {
+    // ((n - 1) / (n + 1))^2 = 0.04
+    for fresnel in [Fresnel::Exact, Fresnel::Schlick] {
+        let r = fresnel.reflectance(1.0, 1.5);
+        assert!((r - 0.04).abs() < 1e-4, "{:?}: {}", fresnel, r);
+
+        // 안에서 밖으로 나갈 때도 같음
+        let r = fresnel.reflectance(1.0, 1.0 / 1.5);
+        assert!((r - 0.04).abs() < 1e-4, "{:?}: {}", fresnel, r);
+    }
+}
+
+#[test]
+fn grazing_incidence_reflects_everything() {
+    for fresnel in [Fresnel::Exact, Fresnel::Schlick] {
+        let r = fresnel.reflectance(1e-4, 1.5);
+        assert!(r > 0.99, "{:?}: {}", fresnel, r);
+    }
+}
+
+#[test]
+fn reflectance_stays_in_unit_range() {
+    for eta in [1.0 / 2.4, 1.0 / 1.5, 1.0 / 1.33, 1.0, 1.33, 1.5, 2.4] {
+        for k in 0..=100 {
+            let cos_i = k as f32 / 100.0;
+
+            for r in [fresnel_dielectric(cos_i, eta), fresnel_schlick(cos_i, eta)] {
+                // 반사 R 과 굴절 T = 1 - R 모두 [0, 1]
+                assert!((0.0..=1.0).contains(&r), "eta {} cos {}: {}", eta, cos_i, r);
+            }
+        }
+    }
+}
+
+#[test]
+fn exact_fresnel_is_reciprocal() {
+    // 같은 경로를 반대로 지나가도 반사율이 같음
+    let eta = 1.5;
+
+    for k in 1..=20 {
+        let cos_i = k as f32 / 20.0;
+        let sin_t = (1.0 - cos_i * cos_i).sqrt() / eta;
+        let cos_t = (1.0 - sin_t * sin_t).sqrt();
+
+        let forward = fresnel_dielectric(cos_i, eta);
+        let backward = fresnel_dielectric(cos_t, 1.0 / eta);
+        assert!((forward - backward).abs() < 1e-5, "cos {}: {} vs {}", cos_i, forward, backward);
+    }
+}
+
+#[test]
+fn total_internal_reflection_beyond_critical_angle() {
+    // 유리 -> 공기의 임계각은 약 41.8°
+    let eta = 1.0 / 1.5;
+    let cos_i = 50.0f32.to_radians().cos();
+
+    assert_eq!(fresnel_dielectric(cos_i, eta), 1.0);
+    assert_eq!(fresnel_schlick(cos_i, eta), 1.0);
+
+    let n = cgmath::vec3(0.0, 1.0, 0.0);
+    let dir = cgmath::vec3(50.0f32.to_radians().sin(), -cos_i, 0.0);
+    assert!(refract(dir, n, eta).is_none());
+}
+
+#[test]
+fn refraction_follows_snell() {
+    let n = cgmath::vec3(0.0, 1.0, 0.0);
+    let dir = cgmath::vec3(0.6, -0.8, 0.0);
+
+    let t = refract(dir, n, 1.5).unwrap();
+    let sin_i = dir.cross(n).magnitude();
+    let sin_t = t.cross(n).magnitude();
+
+    assert!((sin_i - 1.5 * sin_t).abs() < 1e-5);
+    assert!(t.y < 0.0);
+
+    let r = reflect(dir, n);
+    assert!((r - cgmath::vec3(0.6, 0.8, 0.0)).magnitude() < 1e-6);
+}
+
+// 흰색 ambient 로 된 큰 구 안에 유리 구를 놓으면, 반사와 굴절로 나뉜 빛을 다시 더해서 흰색이 되어야 함
+#[test]
+fn glass_sphere_conserves_energy() {
+    for fresnel in [Fresnel::Exact, Fresnel::Schlick] {
+        let room = (cgmath::vec3(0.0, 0.0, 0.0), 20.0, common::white());
+        let glass = (cgmath::vec3(0.0, 0.0, 1.5), 1.0, Material { transparency: 1.0, ior: 1.5, fresnel, ..Material::default() });
+        let scene = common::spheres(Camera::default(), vec![room, glass]);
+
+        let img_buff = common::render_with(&common::scene_raytracer(scene, 32, 32));
+
+        // 재귀 깊이 제한으로 유리 안에서 여러 번 반사된 아주 작은 양만 사라짐
+        for p in img_buff.pixels() {
+            assert!(p[0] >= 245, "{:?}: pixel value {}", fresnel, p[0]);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{mean, render, FLOOR};
+use raytracing_rs::path::{power_heuristic, PathTracing};
+use raytracing_rs::whitted::Whitted;
+
+// 스스로 빛을 내는 (amb) 방 안: L = Le + albedo * L 이므로 L = Le / (1 - albedo)
+// 방 안 어디를 봐도, 유리를 통해 봐도 같은 밝기
+#[test]
+fn furnace_converges_to_geometric_series() {
+    let src = r#"
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 0.0]
+radius = 5.0
+
+[objects.material]
+amb = [0.25, 0.125, 0.1]
+diff = [0.5, 0.75, 0.8]
+
+# 흡수하지 않는 유리는 결과를 바꾸지 않음
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 2.0]
+radius = 0.8
+
+[objects.material]
+transparency = 1.0
+roughness = 0.2
+"#;
+
+    let img_buff = render(src, Box::new(PathTracing::default()), 16, 32);
+
+    // 0.5, 0.5, 0.5
+    for (channel, expected) in [(0, 0.5), (1, 0.5), (2, 0.5)] {
+        let m = mean(&img_buff, channel) / 255.0;
+        assert!((m - expected).abs() < 0.03, "channel {}: {}", channel, m);
+    }
+}
+
+// 반지름 R 인 구 조명이 D 만큼 위에 있으면, 바로 아래 흰 바닥의 밝기는 Le * (R / D)^2
+// (조명 샘플링과 BSDF 샘플링을 MIS 로 합친 결과가 맞는지)
+#[test]
+fn sphere_light_on_a_lambert_floor() {
+    let src = FLOOR.replace(
+        "LIGHT",
+        r#"
+[[lights]]
+type = "sphere"
+pos = [0.0, 1.5, 0.0]
+radius = 0.5
+intensity = 4.5
+"#,
+    );
+
+    let img_buff = render(&src, Box::new(PathTracing::default()), 8, 64);
+
+    let m = mean(&img_buff, 0) / 255.0;
+    assert!((m - 0.5).abs() < 0.03, "{}", m);
+}
+
+// 점 조명은 조명 샘플링만으로 계산: albedo / PI * I / h^2
+#[test]
+fn point_light_on_a_lambert_floor() {
+    let src = FLOOR.replace(
+        "LIGHT",
+        r#"
+[[lights]]
+type = "point"
+pos = [0.0, 2.0, 0.0]
+intensity = 6.2832
+attenuation = [0.0, 0.0, 1.0]
+"#,
+    );
+
+    let img_buff = render(&src, Box::new(PathTracing::default()), 8, 4);
+
+    let m = mean(&img_buff, 0) / 255.0;
+    assert!((m - 0.5).abs() < 0.02, "{}", m);
+}
+
+// Whitted 와 path tracing 은 같은 장면의 diffuse 바닥을 같은 밝기로 그림 (바닥끼리는 서로 비추지 않음)
+#[test]
+fn whitted_and_path_agree_on_a_diffuse_floor() {
+    let point = "[[lights]]\ntype = \"point\"\npos = [0.0, 2.0, 0.0]\nintensity = 5.0\n";
+    let sphere = "[[lights]]\ntype = \"sphere\"\npos = [0.0, 1.5, 0.0]\nradius = 0.5\nintensity = 3.0\n";
+
+    for (name, light) in [("point", point), ("sphere", sphere)] {
+        let src = FLOOR.replace("LIGHT", light);
+
+        let whitted = mean(&render(&src, Box::new(Whitted::default()), 8, 4), 0) / 255.0;
+        let path = mean(&render(&src, Box::new(PathTracing::default()), 8, 64), 0) / 255.0;
+
+        assert!(whitted > 0.2, "{}: {}", name, whitted);
+        assert!((whitted - path).abs() < 0.03, "{}: whitted {} / path {}", name, whitted, path);
+    }
+}
+
+#[test]
+fn power_heuristic_weights_sum_to_one() {
+    for (a, b) in [(1.0, 1.0), (0.1, 3.0), (5.0, 0.0)] {
+        let sum = power_heuristic(a, b) + power_heuristic(b, a);
+        assert!((sum - 1.0).abs() < 1e-6);
+    }
+
+    assert_eq!(power_heuristic(0.0, 0.0), 0.0);
+}
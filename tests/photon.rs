@@ -0,0 +1,193 @@
+mod common;
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use raytracing_rs::photon::{Photon, PhotonMap, PhotonMapping};
+use raytracing_rs::random::Pcg32;
+use raytracing_rs::sampler::{Sampler, SamplerKind};
+use raytracing_rs::whitted::Whitted;
+
+fn render(src: &str, photon_mapping: Option<PhotonMapping>) -> image::RgbImage {
+    let mut raytracer = common::raytracer(src, Box::new(Whitted::default()), 16, 1);
+    raytracer.photon_map = photon_mapping.map(|p| p.build(&raytracer));
+
+    common::render_with(&raytracer)
+}
+
+// kd-tree 로 찾은 photon 과 모두 비교해서 찾은 photon 이 같음
+#[test]
+fn gather_finds_the_same_photons_as_brute_force() {
+    let mut rng = Pcg32::new(7, 0);
+    let photons: Vec<Photon> = (0..2000)
+        .map(|i| Photon {
+            pos: cgmath::vec3(rng.next_f32(), rng.next_f32() * 0.1, rng.next_f32() * 2.0),
+            dir: cgmath::vec3(0.0, if i % 4 == 0 { -1.0 } else { 1.0 }, 0.0),
+            power: cgmath::vec3(1.0, 1.0, 1.0),
+        })
+        .collect();
+
+    let map = PhotonMap::new(photons.clone(), 0.15);
+    assert_eq!(map.len(), photons.len());
+
+    let up = cgmath::vec3(0.0, 1.0, 0.0);
+
+    for _ in 0..50 {
+        let point = cgmath::vec3(rng.next_f32(), 0.05, rng.next_f32() * 2.0);
+
+        let mut found = 0;
+        map.gather(point, up, |_| found += 1);
+
+        // 아래에서 온 photon 은 빼고
+        let expected = photons
+            .iter()
+            .filter(|p| (p.pos.x - point.x).powi(2) + (p.pos.y - point.y).powi(2) + (p.pos.z - point.z).powi(2) <= 0.15 * 0.15 && p.dir.y > 0.0)
+            .count();
+
+        assert_eq!(found, expected);
+    }
+}
+
+// 면적당 flux: 1 x 1 안에 고르게 있는 10000 개의 photon (합 1) 이면 어디서나 1
+#[test]
+fn irradiance_of_evenly_spread_photons() {
+    let photons: Vec<Photon> = (0..10000)
+        .map(|i| Photon {
+            pos: cgmath::vec3((i % 100) as f32 / 100.0 + 0.005, 0.0, (i / 100) as f32 / 100.0 + 0.005),
+            dir: cgmath::vec3(0.0, 1.0, 0.0),
+            power: cgmath::vec3(1e-4, 2e-4, 0.0),
+        })
+        .collect();
+
+    let map = PhotonMap::new(photons, 0.1);
+    let e = map.irradiance(cgmath::vec3(0.5, 0.0, 0.5), cgmath::vec3(0.0, 1.0, 0.0));
+
+    assert!((e.x - 1.0).abs() < 0.05, "{:?}", e);
+    assert!((e.y - 2.0).abs() < 0.1, "{:?}", e);
+    assert_eq!(e.z, 0.0);
+}
+
+// 점 조명과 바닥 사이의 유리 구 (ball lens) 가 바로 아래에 빛을 모음
+const LENS: &str = r#"
+[camera]
+pos = [0.0, 4.0, -4.0]
+look_at = [0.0, 0.0, 0.0]
+up = [0.0, 1.0, 0.0]
+fov = 30.0
+
+[[lights]]
+type = "point"
+pos = [0.0, 3.0, 0.0]
+intensity = 9.0
+attenuation = [0.0, 0.0, 1.0]
+
+[[objects]]
+type = "square"
+vertices = [[-10.0, 0.0, -10.0], [-10.0, 0.0, 10.0], [10.0, 0.0, 10.0], [10.0, 0.0, -10.0]]
+
+[objects.material]
+diff = [0.3, 0.3, 0.3]
+
+GLASS
+"#;
+
+const GLASS: &str = r#"
+[[objects]]
+type = "sphere"
+center = [0.0, 1.5, 0.0]
+radius = 0.5
+
+[objects.material]
+transparency = 1.0
+ior = 1.5
+"#;
+
+#[test]
+fn glass_sphere_focuses_a_caustic() {
+    let plain = render(&LENS.replace("GLASS", ""), None);
+    let caustic = render(&LENS.replace("GLASS", GLASS), Some(PhotonMapping { photons: 20_000, radius: 0.1 }));
+
+    // 바로 아래는 유리가 없을 때보다 훨씬 밝음
+    let center = caustic.get_pixel(8, 8)[0] as f32;
+    assert!(center > 2.0 * plain.get_pixel(8, 8)[0] as f32, "{} / {}", center, plain.get_pixel(8, 8)[0]);
+
+    // 유리 구의 그림자 밖은 그대로 (유리를 지나온 빛을 두 번 더하지 않음)
+    for (i, j) in [(0, 15), (15, 15), (0, 0), (15, 0)] {
+        let (a, b) = (plain.get_pixel(i, j)[0] as i32, caustic.get_pixel(i, j)[0] as i32);
+        assert!((a - b).abs() <= 1, "({}, {}): {} / {}", i, j, a, b);
+    }
+}
+
+// photon map 없이는 예전처럼 유리가 transparency 만큼 빛을 통과시킴
+#[test]
+fn without_a_photon_map_glass_lets_light_through() {
+    let plain = render(&LENS.replace("GLASS", ""), None);
+    let glass = render(&LENS.replace("GLASS", GLASS), None);
+
+    assert_eq!(plain.get_pixel(8, 8), glass.get_pixel(8, 8));
+}
+
+// 여러 스레드로 내보내도 photon 번호 순서로 합쳐서 같은 photon map
+#[test]
+fn photon_map_does_not_depend_on_thread_count() {
+    let src = LENS.replace("GLASS", GLASS);
+    let photon_mapping = PhotonMapping { photons: 20_000, radius: 0.1 };
+
+    let build = |threads| {
+        let mut raytracer = common::raytracer(&src, Box::new(Whitted::default()), 16, 1);
+        raytracer.threads = threads;
+        raytracer.photon_map = Some(photon_mapping.build(&raytracer));
+        (raytracer.photon_map.as_ref().unwrap().len(), common::render_with(&raytracer))
+    };
+
+    let (len, single) = build(1);
+    assert!(len > 0);
+
+    for threads in [2, 3] {
+        let (n, img_buff) = build(threads);
+        assert_eq!(n, len, "{} threads", threads);
+        assert!(img_buff == single, "{} threads differ", threads);
+    }
+}
+
+// 샘플을 꺼낸 픽셀을 기록하는 sampler
+struct RecordingSampler {
+    inner: Box<dyn Sampler>,
+    pixels: Arc<Mutex<HashSet<[u32; 2]>>>,
+}
+
+impl Sampler for RecordingSampler {
+    fn sample(&self, pixel: [u32; 2], index: u32, dimension: u32) -> f32 {
+        self.pixels.lock().unwrap().insert(pixel);
+        self.inner.sample(pixel, index, dimension)
+    }
+}
+
+// photon 은 화면 픽셀의 샘플을 쓰지 않으므로 픽셀 (0, 0) 의 샘플 개수가 바뀌어도 photon map 이 같음
+#[test]
+fn photon_emission_does_not_share_pixel_samples() {
+    let src = LENS.replace("GLASS", GLASS);
+    let photon_mapping = PhotonMapping { photons: 5_000, radius: 0.1 };
+
+    let build = |spp| {
+        let pixels = Arc::new(Mutex::new(HashSet::new()));
+
+        let mut raytracer = common::raytracer(&src, Box::new(Whitted::default()), 16, spp);
+        raytracer.sampler = Box::new(RecordingSampler { inner: SamplerKind::Sobol.create(0), pixels: pixels.clone() });
+        let map = photon_mapping.build(&raytracer);
+
+        // 바닥에 모인 caustic 의 photon
+        let mut power = cgmath::vec3(0.0, 0.0, 0.0);
+        map.gather(cgmath::vec3(0.0, 0.0, 0.0), cgmath::vec3(0.0, 1.0, 0.0), |p| power += p.power);
+
+        let pixels = pixels.lock().unwrap().clone();
+        ((map.len(), power), pixels)
+    };
+
+    let (one, pixels) = build(1);
+    let (many, _) = build(64);
+
+    assert!(one.0 > 0);
+    assert_eq!(one, many);
+    assert!(!pixels.contains(&[0, 0]), "{:?}", pixels);
+}
@@ -0,0 +1,74 @@
+use raytracing_rs::sampler::{SampleStream, Sampler, SamplerKind};
+
+const KINDS: [SamplerKind; 4] = [SamplerKind::Random, SamplerKind::Halton, SamplerKind::Sobol, SamplerKind::BlueNoise];
+
+#[test]
+fn samples_are_reproducible_and_in_range() {
+    for kind in KINDS {
+        let a = kind.create(7);
+        let b = kind.create(7);
+
+        for index in 0..64 {
+            for dimension in 0..80 {
+                let u = a.sample([3, 5], index, dimension);
+
+                assert!((0.0..1.0).contains(&u), "{:?}: {} out of range", kind, u);
+                assert_eq!(u, b.sample([3, 5], index, dimension));
+            }
+        }
+    }
+}
+
+#[test]
+fn sobol_first_samples_are_stratified() {
+    let sampler = SamplerKind::Sobol.create(1);
+
+    // Owen scrambling 해도 처음 16 개는 4x4 격자 칸마다 하나씩
+    for pixel in [[0, 0], [10, 3], [99, 42]] {
+        for dimension in [0, 2, 10] {
+            let mut cells = [false; 16];
+
+            for index in 0..16 {
+                let mut samples = SampleStream::new(sampler.as_ref(), pixel, index);
+                for _ in 0..dimension {
+                    samples.next_1d();
+                }
+                let u = samples.next_2d();
+
+                let cell = (u.y * 4.0) as usize * 4 + (u.x * 4.0) as usize;
+                assert!(!cells[cell], "two samples in cell {} (pixel {:?}, dimension {})", cell, pixel, dimension);
+                cells[cell] = true;
+            }
+        }
+    }
+}
+
+// 여러 픽셀에서 16 개 샘플로 구한 x * y 의 평균 (정답 0.25) 의 RMS 오차
+fn integration_error(sampler: &dyn Sampler) -> f32 {
+    let mut sum_sq = 0.0;
+    let pixels = 256;
+
+    for p in 0..pixels {
+        let mut estimate = 0.0;
+
+        for index in 0..16 {
+            let mut samples = SampleStream::new(sampler, [p % 16, p / 16], index);
+            let u = samples.next_2d();
+            estimate += u.x * u.y / 16.0;
+        }
+
+        sum_sq += (estimate - 0.25) * (estimate - 0.25);
+    }
+
+    (sum_sq / pixels as f32).sqrt()
+}
+
+#[test]
+fn low_discrepancy_samplers_beat_random() {
+    let random = integration_error(SamplerKind::Random.create(0).as_ref());
+
+    for kind in [SamplerKind::Halton, SamplerKind::Sobol, SamplerKind::BlueNoise] {
+        let error = integration_error(kind.create(0).as_ref());
+        assert!(error < random * 0.5, "{:?}: error {} vs random {}", kind, error, random);
+    }
+}
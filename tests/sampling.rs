@@ -0,0 +1,148 @@
+mod common;
+
+use raytracing_rs::camera::Camera;
+use raytracing_rs::filter::{Filter, FilterKind};
+use raytracing_rs::raytracer::Raytracer;
+use raytracing_rs::sampling::{AdaptiveSampling, PixelSampling};
+
+// 검은 배경 위의 흰 구 (ambient 만)
+fn sphere_raytracer() -> Raytracer {
+    let scene = common::spheres(Camera::default(), vec![(cgmath::vec3(0.0, 0.0, 3.0), 1.0, common::white())]);
+
+    common::scene_raytracer(scene, 40, 40)
+}
+
+fn render_sphere(samples_per_pixel: u32, pixel_sampling: PixelSampling) -> image::RgbImage {
+    let mut raytracer = sphere_raytracer();
+    raytracer.samples_per_pixel = samples_per_pixel;
+    raytracer.pixel_sampling = pixel_sampling;
+
+    common::render_with(&raytracer)
+}
+
+fn render_sphere_filtered(filter: Filter) -> image::RgbImage {
+    let mut raytracer = sphere_raytracer();
+    raytracer.filter = filter;
+
+    common::render_with(&raytracer)
+}
+
+fn count_partial(img: &image::RgbImage) -> usize {
+    img.pixels().filter(|p| p[0] > 0 && p[0] < 255).count()
+}
+
+#[test]
+fn single_sample_has_hard_edges() {
+    let img = render_sphere(1, PixelSampling::Regular);
+
+    assert_eq!(count_partial(&img), 0);
+}
+
+#[test]
+fn edge_pixels_get_fractional_coverage() {
+    for pixel_sampling in [PixelSampling::Regular, PixelSampling::Stratified, PixelSampling::Random] {
+        let img = render_sphere(16, pixel_sampling);
+
+        // 구의 테두리 픽셀만 일부 샘플이 구에 닿음
+        let partial = count_partial(&img);
+        assert!(partial > 20, "{:?}: only {} partially covered pixels", pixel_sampling, partial);
+
+        // 구의 중심과 구석은 그대로
+        assert_eq!(img.get_pixel(20, 20)[0], 255);
+        assert_eq!(img.get_pixel(0, 0)[0], 0);
+    }
+}
+
+#[test]
+fn regular_grid_coverage_is_a_multiple_of_sample_weight() {
+    // 2x2 격자면 한 픽셀의 값은 0, 1/4, 2/4, 3/4, 1 중 하나
+    // 정사각형이 아닌 2x3 격자, 2x2 격자 + 남는 샘플 1 개도 샘플 하나의 무게 1/n 단위
+    for spp in [4, 6, 5] {
+        let img = render_sphere(spp, PixelSampling::Regular);
+
+        for p in img.pixels() {
+            let coverage = p[0] as f32 / 255.0 * spp as f32;
+            assert!((coverage - coverage.round()).abs() < 0.05, "spp {}: unexpected value {}", spp, p[0]);
+        }
+    }
+}
+
+// 격자로 나누어떨어지지 않는 샘플 수에서도 빈 칸 없이 픽셀 전체에 고르게 퍼짐 (평균이 픽셀 중심)
+#[test]
+fn regular_offsets_are_centered_for_any_sample_count() {
+    for n in 1..=10 {
+        let offsets: Vec<_> = (0..n).map(|k| PixelSampling::Regular.offset(k, n, cgmath::vec2(0.0, 0.0))).collect();
+        let mean = offsets.iter().fold(cgmath::vec2(0.0, 0.0), |sum, o| sum + o) / n as f32;
+
+        assert!((mean.x - 0.5).abs() < 1e-5 && (mean.y - 0.5).abs() < 1e-5, "n = {}: mean {:?}", n, mean);
+    }
+}
+
+#[test]
+fn adaptive_sampling_refines_only_edges() {
+    let mut raytracer = sphere_raytracer();
+    raytracer.pixel_sampling = PixelSampling::Stratified;
+    raytracer.adaptive = Some(AdaptiveSampling { min_samples: 4, max_samples: 64, threshold: 0.01 });
+
+    let mut img_buff = image::RgbImage::new(40, 40);
+    let mut sample_map = image::GrayImage::new(40, 40);
+    raytracer.render_with_sample_map(&mut img_buff, Some(&mut sample_map));
+
+    // 평탄한 곳은 처음 4 개로 끝나고 (64 개 중 4 개 = 15), 테두리만 더 많이 샘플링
+    assert_eq!(sample_map.get_pixel(20, 20)[0], 15);
+    assert_eq!(sample_map.get_pixel(0, 0)[0], 15);
+
+    let refined = sample_map.pixels().filter(|p| p[0] > 15).count();
+    assert!(refined > 20, "only {} refined pixels", refined);
+    assert!(refined < 40 * 40 / 4, "{} refined pixels", refined);
+
+    // 테두리 픽셀은 부분적으로 덮임
+    assert!(count_partial(&img_buff) > 20);
+}
+
+// max_samples 가 min_samples 보다 작으면 max_samples 개만 쏨
+#[test]
+fn adaptive_sampling_never_exceeds_max_samples() {
+    let mut fixed = sphere_raytracer();
+    fixed.pixel_sampling = PixelSampling::Stratified;
+    fixed.samples_per_pixel = 4;
+
+    let expected = common::render_with(&fixed);
+
+    let mut raytracer = sphere_raytracer();
+    raytracer.pixel_sampling = PixelSampling::Stratified;
+    raytracer.adaptive = Some(AdaptiveSampling { min_samples: 8, max_samples: 4, threshold: 0.0 });
+
+    let mut img_buff = image::RgbImage::new(40, 40);
+    let mut sample_map = image::GrayImage::new(40, 40);
+    raytracer.render_with_sample_map(&mut img_buff, Some(&mut sample_map));
+
+    assert!(img_buff == expected);
+    assert!(sample_map.pixels().all(|p| p[0] == 255));
+}
+
+#[test]
+fn filter_weights() {
+    for kind in [FilterKind::Box, FilterKind::Tent, FilterKind::Gaussian, FilterKind::Mitchell] {
+        let filter = Filter::new(kind);
+
+        assert!(filter.weight(0.0, 0.0) > 0.0);
+        assert_eq!(filter.weight(filter.radius + 0.01, 0.0), 0.0);
+        assert_eq!(filter.weight(0.3, -0.2), filter.weight(-0.3, 0.2));
+    }
+
+    // Mitchell 은 가장자리 근처에 음수 부분이 있음
+    let mitchell = Filter::new(FilterKind::Mitchell);
+    assert!(mitchell.weight(1.5, 0.0) < 0.0);
+}
+
+#[test]
+fn wide_filters_soften_edges() {
+    // 픽셀 중심마다 샘플 1 개: box 는 경계가 딱딱하고, 넓은 필터는 이웃 픽셀의 샘플이 섞여서 부드러움
+    assert_eq!(count_partial(&render_sphere_filtered(Filter::new(FilterKind::Box))), 0);
+
+    for kind in [FilterKind::Tent, FilterKind::Gaussian] {
+        let partial = count_partial(&render_sphere_filtered(Filter { kind, radius: 1.5 }));
+        assert!(partial > 20, "{:?}: only {} partially covered pixels", kind, partial);
+    }
+}
@@ -0,0 +1,234 @@
+mod common;
+
+use std::path::Path;
+
+use raytracing_rs::scene::Scene;
+
+const SHARED_MATERIAL: &str = r#"
+[materials.white]
+amb = [1.0, 1.0, 1.0]
+
+[[objects]]
+type = "sphere"
+center = [-0.5, 0.0, 2.0]
+radius = 0.3
+material = "white"
+
+[[objects]]
+type = "triangle"
+vertices = [[0.2, -0.3, 2.0], [0.5, 0.3, 2.0], [0.8, -0.3, 2.0]]
+material = "white"
+
+[[objects]]
+type = "sphere"
+center = [0.0, -0.6, 2.0]
+radius = 0.1
+
+[objects.material]
+amb = [0.0, 1.0, 0.0]
+"#;
+
+fn error_chain(src: &str) -> String {
+    let err = Scene::parse(src, Path::new(".")).err().expect("scene should not parse");
+    format!("{:#}", err)
+}
+
+#[test]
+fn named_materials_are_shared_between_objects() {
+    let scene = common::parse(SHARED_MATERIAL);
+    assert_eq!(scene.objects.len(), 3);
+
+    let img_buff = common::render_with(&common::scene_raytracer(scene, 40, 40));
+
+    // 구와 삼각형 모두 같은 흰색, 인라인 재질은 따로
+    let sphere = img_buff.get_pixel(16, 20);
+    let triangle = img_buff.get_pixel(24, 20);
+    let inline = img_buff.get_pixel(20, 25);
+
+    assert_eq!(sphere.0, [255, 255, 255]);
+    assert_eq!(triangle.0, [255, 255, 255]);
+    assert_eq!(inline.0, [0, 255, 0]);
+}
+
+#[test]
+fn unknown_material_name_is_reported_with_line() {
+    let message = error_chain(
+        r#"
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 2.0]
+radius = 0.3
+material = "gold"
+"#,
+    );
+
+    assert!(message.contains("object at line 2"), "{}", message);
+    assert!(message.contains("unknown material 'gold' at line 2"), "{}", message);
+}
+
+#[test]
+fn invalid_named_material_is_reported_by_name() {
+    let message = error_chain(
+        r#"
+[materials.mirror]
+reflection = 1.5
+"#,
+    );
+
+    assert!(message.contains("material 'mirror' at line 2"), "{}", message);
+    assert!(message.contains("reflection must be in [0, 1]"), "{}", message);
+}
+
+#[test]
+fn unknown_inline_material_field_is_reported() {
+    let message = error_chain(
+        r#"
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 2.0]
+radius = 0.3
+
+[objects.material]
+colour = [1.0, 0.0, 0.0]
+"#,
+    );
+
+    assert!(message.contains("colour"), "{}", message);
+}
+
+#[test]
+fn negative_absorption_is_rejected() {
+    let message = error_chain(
+        r#"
+[materials.ink]
+transparency = 1.0
+absorption = [0.5, -0.1, 0.5]
+"#,
+    );
+
+    assert!(message.contains("material 'ink'"), "{}", message);
+    assert!(message.contains("absorption and density must not be negative"), "{}", message);
+}
+
+#[test]
+fn metallic_roughness_fields_need_the_model() {
+    let message = error_chain(
+        r#"
+[materials.gold]
+base_color = [1.0, 0.8, 0.3]
+metallic = 1.0
+"#,
+    );
+
+    assert!(message.contains("material 'gold'"), "{}", message);
+    assert!(message.contains("model = \"metallic-roughness\""), "{}", message);
+
+    let message = error_chain(
+        r#"
+[materials.gold]
+model = "metallic-roughness"
+roughness = 1.5
+"#,
+    );
+
+    assert!(message.contains("roughness must be in [0, 1]"), "{}", message);
+}
+
+#[test]
+fn minimal_scene_parses() {
+    let scene = Scene::parse(
+        r#"
+[[lights]]
+type = "point"
+pos = [0.0, 1.0, 0.0]
+
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 2.0]
+radius = 0.5
+"#,
+        Path::new("."),
+    )
+    .unwrap();
+
+    assert_eq!(scene.lights.len(), 1);
+    assert_eq!(scene.objects.len(), 1);
+}
+
+// 값의 형식이 틀리면 toml 이 그 물체가 시작하는 줄 번호를 알려줌
+#[test]
+fn bad_field_type_is_reported_with_line() {
+    let message = error_chain(
+        r#"
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 2.0]
+radius = "big"
+"#,
+    );
+
+    assert!(message.contains("line 2"), "{}", message);
+    assert!(message.contains("invalid type: string \"big\""), "{}", message);
+}
+
+#[test]
+fn invalid_object_is_reported_with_line() {
+    let message = error_chain(
+        r#"
+[[lights]]
+type = "point"
+pos = [0.0, 1.0, 0.0]
+
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 2.0]
+radius = 0.5
+
+[[objects]]
+type = "sphere"
+center = [0.0, 0.0, 2.0]
+radius = -1.0
+"#,
+    );
+
+    assert!(message.contains("object at line 11"), "{}", message);
+    assert!(message.contains("sphere radius must be positive"), "{}", message);
+}
+
+#[test]
+fn invalid_light_is_reported_with_line() {
+    let message = error_chain(
+        r#"
+[[lights]]
+type = "point"
+pos = [0.0, 1.0, 0.0]
+
+[[lights]]
+type = "spot"
+pos = [0.0, 1.0, 0.0]
+dir = [0.0, -1.0, 0.0]
+cone_angle = 200.0
+"#,
+    );
+
+    assert!(message.contains("light at line 6"), "{}", message);
+    assert!(message.contains("cone_angle must be in [0, 180]"), "{}", message);
+}
+
+#[test]
+fn invalid_camera_is_reported_with_line() {
+    let message = error_chain(
+        r#"
+[[lights]]
+type = "point"
+pos = [0.0, 1.0, 0.0]
+
+[camera]
+projection = "orthographic"
+ortho_height = -1.0
+"#,
+    );
+
+    assert!(message.contains("camera at line 6"), "{}", message);
+    assert!(message.contains("ortho_height must be positive"), "{}", message);
+}
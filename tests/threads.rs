@@ -0,0 +1,41 @@
+mod common;
+
+use raytracing_rs::filter::{Filter, FilterKind};
+use raytracing_rs::raytracer::Raytracer;
+use raytracing_rs::sampling::PixelSampling;
+use raytracing_rs::scene::Scene;
+
+fn render_with_threads(raytracer: &mut Raytracer, threads: usize) -> image::RgbImage {
+    raytracer.threads = threads;
+    common::render_with(raytracer)
+}
+
+#[test]
+fn output_does_not_depend_on_thread_count() {
+    let scene = Scene::load("./scenes/default.toml").unwrap();
+    let mut raytracer = common::scene_raytracer(scene, 70, 50);
+
+    let single = render_with_threads(&mut raytracer, 1);
+
+    for threads in [2, 3, 8] {
+        assert!(render_with_threads(&mut raytracer, threads) == single, "{} threads differ from 1 thread", threads);
+    }
+}
+
+#[test]
+fn wide_filters_do_not_depend_on_thread_count() {
+    for kind in [FilterKind::Tent, FilterKind::Gaussian, FilterKind::Mitchell] {
+        let scene = Scene::load("./scenes/default.toml").unwrap();
+        let mut raytracer = common::scene_raytracer(scene, 40, 36);
+        raytracer.samples_per_pixel = 4;
+        raytracer.pixel_sampling = PixelSampling::Stratified;
+        raytracer.filter = Filter::new(kind);
+
+        // tile 경계를 넘어가는 샘플도 항상 같은 순서로 더해짐
+        let single = render_with_threads(&mut raytracer, 1);
+
+        for threads in [2, 5] {
+            assert!(render_with_threads(&mut raytracer, threads) == single, "{:?}: {} threads differ", kind, threads);
+        }
+    }
+}